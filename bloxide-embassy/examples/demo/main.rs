@@ -0,0 +1,98 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::{
+        demo_root::{components::*, ext_state::*},
+        supervisor::{components::*, ext_state::*},
+    },
+    components::*,
+    messaging::*,
+    state_machine::*,
+    std_exports::*,
+};
+use bloxide_embassy::{EmbassyMessageHandle, EmbassyRuntime, DEFAULT_CHANNEL_SIZE};
+use embassy_executor::Spawner;
+use log::*;
+
+#[embassy_executor::main]
+async fn main(spawner: Spawner) {
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .parse_default_env()
+        .init();
+    EmbassyRuntime::init(spawner.make_send());
+
+    // Create the Supervisor handle first so it can be passed to the Root
+    let (supervisor_supervisor_handle, supervisor_supervisor_rx) =
+        EmbassyMessageHandle::create_channel_with_size(11, DEFAULT_CHANNEL_SIZE);
+
+    let (root_standard_handle, root_standard_rx) =
+        EmbassyMessageHandle::create_channel_with_size(1, DEFAULT_CHANNEL_SIZE);
+
+    let (root_counter_handle, root_counter_rx) =
+        EmbassyMessageHandle::create_channel_with_size(2, DEFAULT_CHANNEL_SIZE);
+
+    let root_receivers: <RootComponents<EmbassyRuntime> as bloxide_core::Components>::Receivers =
+        RootReceivers {
+            std_rx: root_standard_rx,
+            counter_rx: root_counter_rx,
+        };
+
+    let root_handles: <RootComponents<EmbassyRuntime> as bloxide_core::Components>::Handles =
+        RootHandles {
+            standard_handle: root_standard_handle.clone(),
+            counter_handle: root_counter_handle,
+        };
+
+    let root_init_args = RootInitArgs {
+        supervisor_handle: supervisor_supervisor_handle.clone(),
+        counter_handle: None,
+    };
+
+    let root_extended_state = RootExtState::<EmbassyRuntime>::new(root_init_args);
+
+    let root_blox = Blox::<RootComponents<EmbassyRuntime>>::new(
+        root_receivers,
+        root_extended_state,
+        root_handles,
+    );
+
+    // The embassy executor never returns, exit once the root blox has finished
+    let root_future = Box::pin(async move {
+        Box::new(root_blox).run().await;
+        info!("Main done!");
+        std::process::exit(0);
+    });
+
+    let (supervisor_standard_handle, supervisor_standard_rx) =
+        EmbassyMessageHandle::create_channel_with_size(10, DEFAULT_CHANNEL_SIZE);
+
+    let supervisor_receivers = SupervisorReceivers::<EmbassyRuntime> {
+        standard_receiver: supervisor_standard_rx,
+        supervisor_receiver: supervisor_supervisor_rx,
+    };
+
+    let supervisor_handles = SupervisorHandles::<EmbassyRuntime> {
+        standard_handle: supervisor_standard_handle,
+        supervisor_handle: supervisor_supervisor_handle,
+    };
+
+    let supervisor_init_args = SupervisorInitArgs::<EmbassyRuntime> {
+        root_standard_handle,
+        root_future,
+    };
+
+    let supervisor_extended_state =
+        SupervisorExtendedState::<EmbassyRuntime>::new(supervisor_init_args);
+
+    let supervisor_blox = Blox::<SupervisorComponents<EmbassyRuntime>>::new(
+        supervisor_receivers,
+        supervisor_extended_state,
+        supervisor_handles,
+    );
+
+    EmbassyRuntime::spawn(async move {
+        Box::new(supervisor_blox).run().await;
+        info!("Supervisor finished.");
+    });
+}
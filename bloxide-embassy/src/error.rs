@@ -0,0 +1,28 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::messaging::*;
+use bloxide_core::std_exports::*;
+
+/// Error returned by `try_send` on embassy handles, holding the message that wasn't sent
+pub enum EmbassyTrySendError<P> {
+    Full(Message<P>),
+    Closed(Message<P>),
+}
+
+impl<P> fmt::Debug for EmbassyTrySendError<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmbassyTrySendError::Full(_) => write!(f, "Full(..)"),
+            EmbassyTrySendError::Closed(_) => write!(f, "Closed(..)"),
+        }
+    }
+}
+
+impl<P> EmbassyTrySendError<P> {
+    pub fn kind(&self) -> SendErrorKind {
+        match self {
+            EmbassyTrySendError::Full(_) => SendErrorKind::Full,
+            EmbassyTrySendError::Closed(_) => SendErrorKind::Closed,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! An embassy executor on a host thread, to run bloxes from tests with the `std` feature
+
+use crate::EmbassyRuntime;
+use bloxide_core::components::Runtime;
+use bloxide_core::std_exports::*;
+use core::task::{Context, Poll};
+use embassy_executor::Executor;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex, Once};
+use std::thread;
+
+/// Starts an executor on its own thread and initializes `EmbassyRuntime` with its
+/// spawner.  Only the first call in a process starts one, the executor never returns.
+pub fn start_executor() {
+    static STARTED: Once = Once::new();
+    STARTED.call_once(|| {
+        let (ready, started) = mpsc::channel();
+        thread::Builder::new()
+            .name("bloxide-embassy".to_string())
+            .spawn(move || {
+                let executor: &'static mut Executor = Box::leak(Box::new(Executor::new()));
+                executor.run(|spawner| {
+                    EmbassyRuntime::init(spawner.make_send());
+                    let _ = ready.send(());
+                })
+            })
+            .expect("Failed to spawn the embassy executor thread");
+        started
+            .recv()
+            .expect("Embassy executor thread stopped before starting");
+    });
+}
+
+/// Runs `future` as a task on the executor from `start_executor`, blocking the current
+/// thread until it completes.  A panic in `future` is resumed on the current thread.
+pub fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    // Bloxes share the runtime's task pool, so one future runs at a time
+    static RUNNING: Mutex<()> = Mutex::new(());
+    let _running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+    start_executor();
+
+    let (done, output) = mpsc::channel();
+    let future = CatchUnwind(Box::pin(future));
+    EmbassyRuntime::spawn(async move {
+        let _ = done.send(future.await);
+    });
+    match output.recv() {
+        Ok(Ok(output)) => output,
+        Ok(Err(panic)) => panic::resume_unwind(panic),
+        Err(_) => panic!("Future dropped before completing, the task pool may be full"),
+    }
+}
+
+// Keeps a panic from unwinding through, and stopping, the executor's thread
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match panic::catch_unwind(AssertUnwindSafe(|| self.0.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(panic) => Poll::Ready(Err(panic)),
+        }
+    }
+}
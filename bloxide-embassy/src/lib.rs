@@ -0,0 +1,20 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod error;
+#[cfg(feature = "std")]
+pub mod executor;
+#[cfg(feature = "alloc")]
+pub mod runtime;
+pub mod static_runtime;
+
+pub use error::*;
+#[cfg(feature = "std")]
+pub use executor::*;
+#[cfg(feature = "alloc")]
+pub use runtime::*;
+pub use static_runtime::*;
@@ -0,0 +1,201 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::error::EmbassyTrySendError;
+use alloc::sync::Arc;
+use bloxide_core::components::{DynamicRuntime, Runtime};
+use bloxide_core::messaging::*;
+use bloxide_core::std_exports::*;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+use embassy_executor::SendSpawner;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::channel::Channel;
+use embassy_sync::once_lock::OnceLock;
+use embassy_sync::waitqueue::AtomicWaker;
+use futures_core::Stream;
+use log::*;
+
+/// Maximum number of messages a channel can hold, requested sizes are clamped to this
+pub const CHANNEL_CAPACITY: usize = 32;
+
+pub const DEFAULT_CHANNEL_SIZE: usize = CHANNEL_CAPACITY;
+
+pub const STANDARD_MESSAGE_CHANNEL_SIZE: usize = DEFAULT_CHANNEL_SIZE;
+
+/// Number of bloxes that can be spawned on the runtime at the same time
+pub const BLOX_TASK_POOL_SIZE: usize = 16;
+
+static SPAWNER: OnceLock<SendSpawner> = OnceLock::new();
+
+#[embassy_executor::task(pool_size = BLOX_TASK_POOL_SIZE)]
+async fn blox_task(future: Pin<Box<dyn Future<Output = ()> + Send + 'static>>) {
+    future.await;
+}
+
+#[derive(Clone)]
+pub struct EmbassyRuntime;
+
+impl EmbassyRuntime {
+    /// Sets the spawner bloxes are spawned with, must be called before `spawn`
+    pub fn init(spawner: SendSpawner) {
+        if SPAWNER.init(spawner).is_err() {
+            warn!("EmbassyRuntime already initialized");
+        }
+    }
+}
+
+impl Runtime for EmbassyRuntime {
+    type MessageHandle<P: Send + 'static> = EmbassyMessageHandle<P>;
+
+    fn spawn<F>(f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let Some(spawner) = SPAWNER.try_get() else {
+            error!("EmbassyRuntime::init must be called before spawning");
+            return;
+        };
+        if let Err(e) = spawner.spawn(blox_task(Box::pin(f))) {
+            error!("Failed to spawn blox: {:?}", e);
+        }
+    }
+
+    type ReceiverStream<P: Send + 'static> = EmbassyReceiverStream<P>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        EmbassyReceiverStream { receiver }
+    }
+}
+
+impl DynamicRuntime for EmbassyRuntime {}
+
+// State shared by all handles of a channel and its receiver
+struct Shared<P> {
+    channel: Channel<CriticalSectionRawMutex, Message<P>, CHANNEL_CAPACITY>,
+    // Size requested at creation, at most CHANNEL_CAPACITY
+    size: usize,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+    // Woken when the last sender is dropped so the receiver stream can end
+    closed_waker: AtomicWaker,
+}
+
+pub struct EmbassyMessageHandle<P: Send + 'static> {
+    id: u16,
+    shared: Arc<Shared<P>>,
+}
+
+impl<P: Send + 'static> Clone for EmbassyMessageHandle<P> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::AcqRel);
+        Self {
+            id: self.id,
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<P: Send + 'static> Drop for EmbassyMessageHandle<P> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.closed_waker.wake();
+        }
+    }
+}
+
+impl<P: Send + 'static> fmt::Debug for EmbassyMessageHandle<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EmbassyMessageHandle({})", self.id)
+    }
+}
+
+impl<P: Send + 'static> MessageSender for EmbassyMessageHandle<P> {
+    type PayloadType = P;
+    type SenderType = EmbassyMessageHandle<P>;
+    type ReceiverType = EmbassyReceiver<P>;
+    type ErrorType = EmbassyTrySendError<P>;
+
+    fn try_send(&self, msg: Message<P>) -> Result<(), Self::ErrorType> {
+        if !self.shared.receiver_alive.load(Ordering::Acquire) {
+            return Err(EmbassyTrySendError::Closed(msg));
+        }
+        if self.shared.channel.len() >= self.shared.size {
+            return Err(EmbassyTrySendError::Full(msg));
+        }
+        self.shared.channel.try_send(msg).map_err(|e| match e {
+            embassy_sync::channel::TrySendError::Full(msg) => EmbassyTrySendError::Full(msg),
+        })
+    }
+
+    fn error_kind(error: &Self::ErrorType) -> SendErrorKind {
+        error.kind()
+    }
+
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn create_channel_with_size(id: u16, size: usize) -> (Self, Self::ReceiverType) {
+        if size > CHANNEL_CAPACITY {
+            warn!(
+                "Channel size {} exceeds capacity {}, clamping",
+                size, CHANNEL_CAPACITY
+            );
+        }
+        let shared = Arc::new(Shared {
+            channel: Channel::new(),
+            size: size.min(CHANNEL_CAPACITY),
+            senders: AtomicUsize::new(1),
+            receiver_alive: AtomicBool::new(true),
+            closed_waker: AtomicWaker::new(),
+        });
+        (
+            Self {
+                id,
+                shared: shared.clone(),
+            },
+            EmbassyReceiver { shared },
+        )
+    }
+}
+
+/// Receiving side of an `EmbassyMessageHandle` channel
+pub struct EmbassyReceiver<P: Send + 'static> {
+    shared: Arc<Shared<P>>,
+}
+
+impl<P: Send + 'static> EmbassyReceiver<P> {
+    /// Receives the next message, `None` once every handle has been dropped and the
+    /// channel is empty
+    pub fn poll_receive(&self, cx: &mut Context<'_>) -> Poll<Option<Message<P>>> {
+        if let Poll::Ready(msg) = self.shared.channel.poll_receive(cx) {
+            return Poll::Ready(Some(msg));
+        }
+        self.shared.closed_waker.register(cx.waker());
+        if self.shared.senders.load(Ordering::Acquire) == 0 {
+            // A message may have been sent just before the last handle was dropped
+            return Poll::Ready(self.shared.channel.try_receive().ok());
+        }
+        Poll::Pending
+    }
+}
+
+impl<P: Send + 'static> Drop for EmbassyReceiver<P> {
+    fn drop(&mut self) {
+        self.shared.receiver_alive.store(false, Ordering::Release);
+    }
+}
+
+pub struct EmbassyReceiverStream<P: Send + 'static> {
+    receiver: EmbassyReceiver<P>,
+}
+
+impl<P: Send + 'static> Stream for EmbassyReceiverStream<P> {
+    type Item = Message<P>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_receive(cx)
+    }
+}
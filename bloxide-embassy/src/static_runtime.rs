@@ -0,0 +1,179 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::error::EmbassyTrySendError;
+use bloxide_core::components::Runtime;
+use bloxide_core::messaging::*;
+use bloxide_core::std_exports::*;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::channel::Channel;
+use futures_core::Stream;
+
+/// Number of messages every static channel holds
+pub const STATIC_CHANNEL_CAPACITY: usize = 16;
+
+/// Runtime for targets without a global allocator.
+///
+/// Channels are `static`s created with `static_channel!` and bloxes run by value in
+/// tasks declared with `blox_task!`, so nothing is allocated at runtime.
+///
+/// It doesn't implement `DynamicRuntime`: `spawn` and `create_channel_with_size` panic,
+/// so bloxes that spawn or create channels, like the supervisor, can't run on it.
+///
+/// ```compile_fail
+/// use bloxide_core::blox::supervisor::components::SupervisorComponents;
+/// use bloxide_embassy::EmbassyStaticRuntime;
+///
+/// let _ = core::mem::size_of::<SupervisorComponents<EmbassyStaticRuntime>>();
+/// ```
+#[derive(Clone)]
+pub struct EmbassyStaticRuntime;
+
+impl Runtime for EmbassyStaticRuntime {
+    type MessageHandle<P: Send + 'static> = StaticMessageHandle<P>;
+
+    /// Futures can't be spawned at runtime without alloc, declare a task with `blox_task!`
+    fn spawn<F>(_f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        panic!("EmbassyStaticRuntime can't spawn futures, declare a task with blox_task!");
+    }
+
+    type ReceiverStream<P: Send + 'static> = StaticReceiver<P>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        receiver
+    }
+}
+
+/// A channel stored in a `static`, see `static_channel!`
+pub struct StaticChannel<P: Send + 'static> {
+    channel: Channel<CriticalSectionRawMutex, Message<P>, STATIC_CHANNEL_CAPACITY>,
+    split: AtomicBool,
+    receiver_alive: AtomicBool,
+}
+
+impl<P: Send + 'static> StaticChannel<P> {
+    pub const fn new() -> Self {
+        Self {
+            channel: Channel::new(),
+            split: AtomicBool::new(false),
+            receiver_alive: AtomicBool::new(true),
+        }
+    }
+
+    /// Returns the handle and receiver of the channel.
+    /// Panics if called more than once, a channel has a single receiver.
+    pub fn split(&'static self, id: u16) -> (StaticMessageHandle<P>, StaticReceiver<P>) {
+        if self.split.swap(true, Ordering::AcqRel) {
+            panic!("Static channel {} split more than once", id);
+        }
+        (
+            StaticMessageHandle { id, channel: self },
+            StaticReceiver { channel: self },
+        )
+    }
+}
+
+impl<P: Send + 'static> Default for StaticChannel<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Declares a static channel for a payload type and splits it, returning
+/// `(StaticMessageHandle, StaticReceiver)`.  Each expansion is its own channel.
+#[macro_export]
+macro_rules! static_channel {
+    ($id:expr, $payload:ty) => {{
+        static CHANNEL: $crate::StaticChannel<$payload> = $crate::StaticChannel::new();
+        CHANNEL.split($id)
+    }};
+}
+
+/// Declares an embassy task that runs a blox by value with `RunnableStatic::run_static`.
+/// The task's pool is static, spawn it with `spawner.spawn(name(blox))`.
+#[macro_export]
+macro_rules! blox_task {
+    ($name:ident, $blox:ty) => {
+        #[embassy_executor::task]
+        async fn $name(blox: $blox) {
+            ::bloxide_core::components::RunnableStatic::run_static(blox).await
+        }
+    };
+}
+
+pub struct StaticMessageHandle<P: Send + 'static> {
+    id: u16,
+    channel: &'static StaticChannel<P>,
+}
+
+impl<P: Send + 'static> Clone for StaticMessageHandle<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Send + 'static> Copy for StaticMessageHandle<P> {}
+
+impl<P: Send + 'static> fmt::Debug for StaticMessageHandle<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StaticMessageHandle({})", self.id)
+    }
+}
+
+impl<P: Send + 'static> MessageSender for StaticMessageHandle<P> {
+    type PayloadType = P;
+    type SenderType = StaticMessageHandle<P>;
+    type ReceiverType = StaticReceiver<P>;
+    type ErrorType = EmbassyTrySendError<P>;
+
+    fn try_send(&self, msg: Message<P>) -> Result<(), Self::ErrorType> {
+        if !self.channel.receiver_alive.load(Ordering::Acquire) {
+            return Err(EmbassyTrySendError::Closed(msg));
+        }
+        self.channel.channel.try_send(msg).map_err(|e| match e {
+            embassy_sync::channel::TrySendError::Full(msg) => EmbassyTrySendError::Full(msg),
+        })
+    }
+
+    fn error_kind(error: &Self::ErrorType) -> SendErrorKind {
+        error.kind()
+    }
+
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Static channels can't be created at runtime, use `static_channel!`
+    fn create_channel_with_size(id: u16, _size: usize) -> (Self, Self::ReceiverType) {
+        panic!(
+            "Channel {} can't be created at runtime without alloc, use static_channel!",
+            id
+        );
+    }
+}
+
+/// Receiving side of a `StaticChannel`.  Static channels are never closed by their
+/// handles, so the stream only ends when the blox stops polling it.
+pub struct StaticReceiver<P: Send + 'static> {
+    channel: &'static StaticChannel<P>,
+}
+
+impl<P: Send + 'static> Drop for StaticReceiver<P> {
+    fn drop(&mut self) {
+        self.channel.receiver_alive.store(false, Ordering::Release);
+    }
+}
+
+impl<P: Send + 'static> Stream for StaticReceiver<P> {
+    type Item = Message<P>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.channel.channel.poll_receive(cx).map(Some)
+    }
+}
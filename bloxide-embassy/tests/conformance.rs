@@ -0,0 +1,5 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_embassy::{block_on, EmbassyRuntime};
+
+bloxide_core::conformance_tests!(EmbassyRuntime, block_on);
@@ -0,0 +1,93 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+// The demo supervisor, root and counter run unchanged on an embassy executor
+
+use bloxide_core::{
+    blox::{
+        demo_root::{
+            components::*,
+            ext_state::*,
+            states::{finished::Finished, RootStates},
+        },
+        supervisor::{components::*, ext_state::*},
+    },
+    components::*,
+    messaging::*,
+    observer::Observer,
+    state_machine::*,
+};
+use bloxide_embassy::{block_on, EmbassyMessageHandle, EmbassyRuntime, DEFAULT_CHANNEL_SIZE};
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex};
+
+type Transitions = Arc<Mutex<Vec<(RootStates, RootStates)>>>;
+
+struct TransitionRecorder(Transitions);
+
+impl Observer<RootComponents<EmbassyRuntime>> for TransitionRecorder {
+    fn on_transition(&mut self, from: &RootStates, to: &RootStates) {
+        self.0.lock().unwrap().push((from.clone(), to.clone()));
+    }
+}
+
+#[test]
+fn demo_runs_to_finished() {
+    let transitions = Transitions::default();
+    let recorded = transitions.clone();
+    block_on(async move {
+        let (supervisor_handle, supervisor_rx) =
+            EmbassyMessageHandle::create_channel_with_size(11, DEFAULT_CHANNEL_SIZE);
+        let (root_standard_handle, std_rx) =
+            EmbassyMessageHandle::create_channel_with_size(1, DEFAULT_CHANNEL_SIZE);
+        let (root_counter_handle, counter_rx) =
+            EmbassyMessageHandle::create_channel_with_size(2, DEFAULT_CHANNEL_SIZE);
+
+        let mut root_blox = Blox::<RootComponents<EmbassyRuntime>>::new(
+            RootReceivers { std_rx, counter_rx },
+            RootExtState::new(RootInitArgs {
+                supervisor_handle: supervisor_handle.clone(),
+                counter_handle: None,
+            }),
+            RootHandles {
+                standard_handle: root_standard_handle.clone(),
+                counter_handle: root_counter_handle,
+            },
+        );
+        root_blox
+            .state_machine
+            .add_observer(TransitionRecorder(recorded));
+
+        // Tells the test the root's run loop has ended
+        let (done, done_rx) = EmbassyMessageHandle::<()>::create_channel_with_size(99, 1);
+        let root_future = Box::pin(async move {
+            Box::new(root_blox).run().await;
+            done.try_send(Message::new(99, ())).unwrap();
+        });
+
+        let (supervisor_standard_handle, standard_receiver) =
+            EmbassyMessageHandle::create_channel_with_size(10, DEFAULT_CHANNEL_SIZE);
+        let supervisor_blox = Blox::<SupervisorComponents<EmbassyRuntime>>::new(
+            SupervisorReceivers {
+                standard_receiver,
+                supervisor_receiver: supervisor_rx,
+            },
+            SupervisorExtendedState::new(SupervisorInitArgs {
+                root_standard_handle,
+                root_future,
+            }),
+            SupervisorHandles {
+                standard_handle: supervisor_standard_handle,
+                supervisor_handle,
+            },
+        );
+        EmbassyRuntime::spawn(Box::new(supervisor_blox).run());
+
+        assert!(poll_fn(|cx| done_rx.poll_receive(cx)).await.is_some());
+    });
+
+    let transitions = transitions.lock().unwrap();
+    assert_eq!(
+        transitions.last().map(|(_, to)| to),
+        Some(&RootStates::Finished(Finished))
+    );
+}
@@ -0,0 +1,18 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+// EmbassyStaticRuntime can't spawn or create channels at runtime, see `DynamicRuntime`
+
+use bloxide_core::{components::Runtime, messaging::*};
+use bloxide_embassy::{EmbassyStaticRuntime, StaticMessageHandle};
+
+#[test]
+#[should_panic(expected = "can't spawn futures")]
+fn spawn_panics() {
+    EmbassyStaticRuntime::spawn(async {});
+}
+
+#[test]
+#[should_panic(expected = "can't be created at runtime")]
+fn create_channel_panics() {
+    let _ = StaticMessageHandle::<u32>::create_channel_with_size(1, 8);
+}
@@ -0,0 +1,105 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::{
+        demo_root::{components::*, ext_state::*},
+        supervisor::{components::*, ext_state::*},
+    },
+    components::*,
+    messaging::*,
+    state_machine::*,
+    std_exports::*,
+};
+use bloxide_sim::{SimMessageHandle, SimRuntime, Simulation, DEFAULT_CHANNEL_SIZE};
+use log::*;
+
+// Runs the demo in a simulation, pass a seed to try another schedule:
+//     cargo run --example sim-demo -- 42
+fn main() {
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .parse_default_env()
+        .init();
+    let seed = std::env::args()
+        .nth(1)
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(0);
+    let mut simulation = Simulation::new(seed);
+
+    // Create the Supervisor handle first so it can be passed to the Root
+    let (supervisor_supervisor_handle, supervisor_supervisor_rx) =
+        SimMessageHandle::create_channel_with_size(11, DEFAULT_CHANNEL_SIZE);
+
+    let (root_standard_handle, root_standard_rx) =
+        SimMessageHandle::create_channel_with_size(1, DEFAULT_CHANNEL_SIZE);
+
+    let (root_counter_handle, root_counter_rx) =
+        SimMessageHandle::create_channel_with_size(2, DEFAULT_CHANNEL_SIZE);
+
+    let root_receivers: <RootComponents<SimRuntime> as bloxide_core::Components>::Receivers =
+        RootReceivers {
+            std_rx: root_standard_rx,
+            counter_rx: root_counter_rx,
+        };
+
+    let root_handles: <RootComponents<SimRuntime> as bloxide_core::Components>::Handles =
+        RootHandles {
+            standard_handle: root_standard_handle.clone(),
+            counter_handle: root_counter_handle,
+        };
+
+    let root_init_args = RootInitArgs {
+        supervisor_handle: supervisor_supervisor_handle.clone(),
+        counter_handle: None,
+    };
+
+    let root_extended_state = RootExtState::<SimRuntime>::new(root_init_args);
+
+    // f) Construct the Root Blox
+    let root_blox =
+        Blox::<RootComponents<SimRuntime>>::new(root_receivers, root_extended_state, root_handles);
+
+    let root_future = Box::pin(async move {
+        Box::new(root_blox).run().await;
+    });
+
+    let (supervisor_standard_handle, supervisor_standard_rx) =
+        SimMessageHandle::create_channel_with_size(10, DEFAULT_CHANNEL_SIZE);
+
+    let supervisor_receivers = SupervisorReceivers::<SimRuntime> {
+        standard_receiver: supervisor_standard_rx,
+        supervisor_receiver: supervisor_supervisor_rx,
+    };
+
+    let supervisor_handles = SupervisorHandles::<SimRuntime> {
+        standard_handle: supervisor_standard_handle,
+        supervisor_handle: supervisor_supervisor_handle,
+    };
+
+    let supervisor_init_args = SupervisorInitArgs::<SimRuntime> {
+        root_standard_handle,
+        root_future,
+    };
+
+    let supervisor_extended_state =
+        SupervisorExtendedState::<SimRuntime>::new(supervisor_init_args);
+
+    let supervisor_blox = Blox::<SupervisorComponents<SimRuntime>>::new(
+        supervisor_receivers,
+        supervisor_extended_state,
+        supervisor_handles,
+    );
+
+    simulation.spawn(async move {
+        Box::new(supervisor_blox).run().await;
+        info!("Supervisor finished.");
+    });
+
+    let steps = simulation.run_until_quiescent();
+    info!(
+        "Quiescent after {} steps at {:?} with seed {}",
+        steps,
+        simulation.now(),
+        seed
+    );
+}
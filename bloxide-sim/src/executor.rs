@@ -0,0 +1,298 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::std_exports::*;
+use log::*;
+use std::cell::{Cell, RefCell};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+
+type Task = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+thread_local! {
+    // The simulation currently running on this thread, set while it runs
+    static CURRENT: RefCell<Option<Rc<Executor>>> = const { RefCell::new(None) };
+}
+
+/// A deterministic, single-threaded executor with a virtual clock.
+///
+/// Every step polls one ready task, chosen by a PRNG seeded with the simulation's
+/// seed, so a run is fully determined by its seed.  When no task is ready the clock
+/// jumps to the next timer.  Running the same system with different seeds explores
+/// different interleavings of its bloxes.
+pub struct Simulation {
+    executor: Rc<Executor>,
+}
+
+impl Simulation {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            executor: Rc::new(Executor {
+                tasks: RefCell::new(Vec::new()),
+                ready: Arc::new(Mutex::new(BTreeSet::new())),
+                timers: RefCell::new(BinaryHeap::new()),
+                timer_seq: Cell::new(0),
+                now: Cell::new(Duration::ZERO),
+                rng: Cell::new(seed),
+                schedule: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Spawns a task, equivalent to `SimRuntime::spawn` from inside the simulation
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.executor.spawn(Box::pin(future));
+    }
+
+    /// Spawns `future` and runs until quiescent, returning its output.  Panics if the
+    /// future hasn't completed by then, as nothing is left that could wake it.
+    pub fn block_on<F>(&mut self, future: F) -> F::Output
+    where
+        F: Future + Send + 'static,
+        F::Output: Send,
+    {
+        let output = Arc::new(Mutex::new(None));
+        let slot = output.clone();
+        self.spawn(async move {
+            let value = future.await;
+            *slot.lock().unwrap() = Some(value);
+        });
+        self.run_until_quiescent();
+        let value = output.lock().unwrap().take();
+        value.expect("Simulation is quiescent but the future is still pending")
+    }
+
+    /// Runs until no task is ready and no timer is pending.  Returns the number of
+    /// tasks polled.
+    pub fn run_until_quiescent(&mut self) -> usize {
+        self.run(None)
+    }
+
+    /// Runs until quiescent or until `duration` of virtual time has passed, whichever
+    /// comes first.  Returns the number of tasks polled.
+    pub fn run_for(&mut self, duration: Duration) -> usize {
+        let limit = self.executor.now.get() + duration;
+        self.run(Some(limit))
+    }
+
+    /// Virtual time since the simulation started
+    pub fn now(&self) -> Duration {
+        self.executor.now.get()
+    }
+
+    /// Number of timers that haven't fired yet
+    pub fn pending_timers(&self) -> usize {
+        self.executor.timers.borrow().len()
+    }
+
+    /// Ids of the tasks polled so far, in order.  Task ids are assigned in spawn order.
+    /// Two runs of the same system with the same seed have the same schedule.
+    pub fn schedule(&self) -> Vec<usize> {
+        self.executor.schedule.borrow().clone()
+    }
+
+    fn run(&mut self, limit: Option<Duration>) -> usize {
+        let previous = CURRENT.with(|c| c.replace(Some(self.executor.clone())));
+        let mut steps = 0;
+        loop {
+            if self.executor.poll_next() {
+                steps += 1;
+                continue;
+            }
+            if !self.executor.advance_clock(limit) {
+                break;
+            }
+        }
+        CURRENT.with(|c| c.replace(previous));
+        trace!("Simulation ran {} steps, now at {:?}", steps, self.now());
+        steps
+    }
+}
+
+/// Spawns a task on the simulation running on this thread
+pub(crate) fn spawn(task: Task) {
+    with_current(|executor| executor.spawn(task));
+}
+
+/// Virtual time of the simulation running on this thread
+pub fn now() -> Duration {
+    with_current(|executor| executor.now.get())
+}
+
+/// Completes once `duration` of virtual time has passed
+pub fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        deadline: now() + duration,
+        registered: false,
+    }
+}
+
+pub struct Sleep {
+    deadline: Duration,
+    // Every waker of a task wakes the same task id, so the timer is added once
+    registered: bool,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        with_current(|executor| {
+            if executor.now.get() >= self.deadline {
+                Poll::Ready(())
+            } else {
+                if !self.registered {
+                    executor.add_timer(self.deadline, cx.waker().clone());
+                    self.registered = true;
+                }
+                Poll::Pending
+            }
+        })
+    }
+}
+
+fn with_current<T>(f: impl FnOnce(&Executor) -> T) -> T {
+    CURRENT.with(|c| {
+        let current = c.borrow();
+        let executor = current
+            .as_ref()
+            .expect("Must be called from a task running in a Simulation");
+        f(executor)
+    })
+}
+
+struct Timer {
+    deadline: Duration,
+    // Orders timers with the same deadline by registration
+    seq: u64,
+    waker: Waker,
+}
+
+impl PartialEq for Timer {
+    fn eq(&self, other: &Self) -> bool {
+        (self.deadline, self.seq) == (other.deadline, other.seq)
+    }
+}
+
+impl Eq for Timer {}
+
+impl PartialOrd for Timer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.deadline, self.seq).cmp(&(other.deadline, other.seq))
+    }
+}
+
+struct Executor {
+    // Indexed by task id, a task is taken out while it is polled and dropped once complete
+    tasks: RefCell<Vec<Option<Task>>>,
+    // Ids of woken tasks, shared with their wakers
+    ready: Arc<Mutex<BTreeSet<usize>>>,
+    timers: RefCell<BinaryHeap<Reverse<Timer>>>,
+    timer_seq: Cell<u64>,
+    now: Cell<Duration>,
+    rng: Cell<u64>,
+    schedule: RefCell<Vec<usize>>,
+}
+
+impl Executor {
+    fn spawn(&self, task: Task) {
+        let mut tasks = self.tasks.borrow_mut();
+        let id = tasks.len();
+        trace!("Spawning task {}", id);
+        tasks.push(Some(task));
+        self.ready.lock().unwrap().insert(id);
+    }
+
+    // Polls one ready task, returns false if none was ready
+    fn poll_next(&self) -> bool {
+        let id = {
+            let mut ready = self.ready.lock().unwrap();
+            if ready.is_empty() {
+                return false;
+            }
+            let index = (self.next_random() % ready.len() as u64) as usize;
+            let id = *ready.iter().nth(index).unwrap();
+            ready.remove(&id);
+            id
+        };
+        // Wakers of completed tasks can still fire
+        let Some(mut task) = self.tasks.borrow_mut()[id].take() else {
+            return true;
+        };
+        self.schedule.borrow_mut().push(id);
+        let waker = Waker::from(Arc::new(TaskWaker {
+            id,
+            ready: self.ready.clone(),
+        }));
+        let mut cx = Context::from_waker(&waker);
+        if task.as_mut().poll(&mut cx).is_pending() {
+            self.tasks.borrow_mut()[id] = Some(task);
+        } else {
+            trace!("Task {} complete", id);
+        }
+        true
+    }
+
+    // Moves the clock to the next timer and wakes every timer due then.
+    // Returns false if there is no timer, or the next one is past `limit`
+    fn advance_clock(&self, limit: Option<Duration>) -> bool {
+        let mut timers = self.timers.borrow_mut();
+        let Some(Reverse(next)) = timers.peek() else {
+            return false;
+        };
+        if limit.is_some_and(|limit| next.deadline > limit) {
+            self.now.set(limit.unwrap());
+            return false;
+        }
+        let now = next.deadline;
+        self.now.set(now);
+        while timers.peek().is_some_and(|Reverse(t)| t.deadline <= now) {
+            let Reverse(timer) = timers.pop().unwrap();
+            timer.waker.wake();
+        }
+        true
+    }
+
+    fn add_timer(&self, deadline: Duration, waker: Waker) {
+        let seq = self.timer_seq.get();
+        self.timer_seq.set(seq + 1);
+        self.timers.borrow_mut().push(Reverse(Timer {
+            deadline,
+            seq,
+            waker,
+        }));
+    }
+
+    // SplitMix64, small and stable so schedules stay reproducible across versions
+    fn next_random(&self) -> u64 {
+        let state = self.rng.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+        self.rng.set(state);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<BTreeSet<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.ready.lock().unwrap().insert(self.id);
+    }
+}
@@ -0,0 +1,5 @@
+// Copyright 2025 Bloxide, all rights reserved
+pub mod executor;
+pub mod runtime;
+pub use executor::*;
+pub use runtime::*;
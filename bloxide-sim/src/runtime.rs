@@ -0,0 +1,178 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::executor;
+use bloxide_core::components::{DynamicRuntime, Runtime};
+use bloxide_core::messaging::*;
+use bloxide_core::std_exports::*;
+use futures_core::Stream;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+pub const DEFAULT_CHANNEL_SIZE: usize = 32;
+
+pub const STANDARD_MESSAGE_CHANNEL_SIZE: usize = DEFAULT_CHANNEL_SIZE;
+
+/// Runtime for bloxes running in a `Simulation`.  Spawning outside a running
+/// simulation panics.
+#[derive(Clone)]
+pub struct SimRuntime;
+
+impl Runtime for SimRuntime {
+    type MessageHandle<P: Send + 'static> = SimMessageHandle<P>;
+
+    fn spawn<F>(f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        executor::spawn(Box::pin(f));
+    }
+
+    type ReceiverStream<P: Send + 'static> = SimReceiver<P>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        receiver
+    }
+}
+
+impl DynamicRuntime for SimRuntime {}
+
+// A bounded FIFO queue shared by the handles of a channel and its receiver
+struct Channel<P> {
+    queue: VecDeque<Message<P>>,
+    capacity: usize,
+    senders: usize,
+    receiver_alive: bool,
+    waker: Option<Waker>,
+}
+
+impl<P> Channel<P> {
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+pub struct SimMessageHandle<P: Send + 'static> {
+    id: u16,
+    channel: Arc<Mutex<Channel<P>>>,
+}
+
+impl<P: Send + 'static> Clone for SimMessageHandle<P> {
+    fn clone(&self) -> Self {
+        self.channel.lock().unwrap().senders += 1;
+        Self {
+            id: self.id,
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<P: Send + 'static> Drop for SimMessageHandle<P> {
+    fn drop(&mut self) {
+        let mut channel = self.channel.lock().unwrap();
+        channel.senders -= 1;
+        if channel.senders == 0 {
+            channel.wake();
+        }
+    }
+}
+
+impl<P: Send + 'static> fmt::Debug for SimMessageHandle<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SimMessageHandle({})", self.id)
+    }
+}
+
+/// Error returned by `SimMessageHandle::try_send`, holding the message that wasn't sent
+pub enum SimTrySendError<P> {
+    Full(Message<P>),
+    Closed(Message<P>),
+}
+
+impl<P> fmt::Debug for SimTrySendError<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimTrySendError::Full(_) => write!(f, "Full(..)"),
+            SimTrySendError::Closed(_) => write!(f, "Closed(..)"),
+        }
+    }
+}
+
+impl<P: Send + 'static> MessageSender for SimMessageHandle<P> {
+    type PayloadType = P;
+    type SenderType = SimMessageHandle<P>;
+    type ReceiverType = SimReceiver<P>;
+    type ErrorType = SimTrySendError<P>;
+
+    fn try_send(&self, msg: Message<P>) -> Result<(), Self::ErrorType> {
+        let mut channel = self.channel.lock().unwrap();
+        if !channel.receiver_alive {
+            return Err(SimTrySendError::Closed(msg));
+        }
+        if channel.queue.len() >= channel.capacity {
+            return Err(SimTrySendError::Full(msg));
+        }
+        channel.queue.push_back(msg);
+        channel.wake();
+        Ok(())
+    }
+
+    fn error_kind(error: &Self::ErrorType) -> SendErrorKind {
+        match error {
+            SimTrySendError::Full(_) => SendErrorKind::Full,
+            SimTrySendError::Closed(_) => SendErrorKind::Closed,
+        }
+    }
+
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn create_channel_with_size(id: u16, size: usize) -> (Self, Self::ReceiverType) {
+        let channel = Arc::new(Mutex::new(Channel {
+            queue: VecDeque::new(),
+            capacity: size.max(1),
+            senders: 1,
+            receiver_alive: true,
+            waker: None,
+        }));
+        (
+            Self {
+                id,
+                channel: channel.clone(),
+            },
+            SimReceiver { channel },
+        )
+    }
+}
+
+/// Receiving side of a `SimMessageHandle` channel, ends once every handle has been
+/// dropped and the channel is empty
+pub struct SimReceiver<P: Send + 'static> {
+    channel: Arc<Mutex<Channel<P>>>,
+}
+
+impl<P: Send + 'static> Drop for SimReceiver<P> {
+    fn drop(&mut self) {
+        self.channel.lock().unwrap().receiver_alive = false;
+    }
+}
+
+impl<P: Send + 'static> Stream for SimReceiver<P> {
+    type Item = Message<P>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut channel = self.channel.lock().unwrap();
+        if let Some(msg) = channel.queue.pop_front() {
+            return Poll::Ready(Some(msg));
+        }
+        if channel.senders == 0 {
+            return Poll::Ready(None);
+        }
+        channel.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
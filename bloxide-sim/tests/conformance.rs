@@ -0,0 +1,14 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_sim::{SimRuntime, Simulation};
+use std::future::Future;
+
+fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send,
+{
+    Simulation::new(0).block_on(future)
+}
+
+bloxide_core::conformance_tests!(SimRuntime, block_on);
@@ -0,0 +1,170 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::{
+        demo_root::{
+            components::*,
+            ext_state::*,
+            states::{finished::Finished, RootStates},
+        },
+        supervisor::{components::*, ext_state::*},
+    },
+    components::*,
+    messaging::*,
+    observer::Observer,
+    state_machine::*,
+};
+use bloxide_sim::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Transitions = Arc<Mutex<Vec<(RootStates, RootStates)>>>;
+
+struct TransitionRecorder(Transitions);
+
+impl Observer<RootComponents<SimRuntime>> for TransitionRecorder {
+    fn on_transition(&mut self, from: &RootStates, to: &RootStates) {
+        self.0.lock().unwrap().push((from.clone(), to.clone()));
+    }
+}
+
+// Spawns the supervisor, root and counter demo, returning the root's transitions
+fn spawn_demo(simulation: &Simulation) -> Transitions {
+    let (supervisor_handle, supervisor_rx) =
+        SimMessageHandle::create_channel_with_size(11, DEFAULT_CHANNEL_SIZE);
+    let (root_standard_handle, std_rx) =
+        SimMessageHandle::create_channel_with_size(1, DEFAULT_CHANNEL_SIZE);
+    let (root_counter_handle, counter_rx) =
+        SimMessageHandle::create_channel_with_size(2, DEFAULT_CHANNEL_SIZE);
+
+    let mut root_blox = Blox::<RootComponents<SimRuntime>>::new(
+        RootReceivers { std_rx, counter_rx },
+        RootExtState::new(RootInitArgs {
+            supervisor_handle: supervisor_handle.clone(),
+            counter_handle: None,
+        }),
+        RootHandles {
+            standard_handle: root_standard_handle.clone(),
+            counter_handle: root_counter_handle,
+        },
+    );
+    let transitions = Transitions::default();
+    root_blox
+        .state_machine
+        .add_observer(TransitionRecorder(transitions.clone()));
+
+    let (supervisor_standard_handle, standard_receiver) =
+        SimMessageHandle::create_channel_with_size(10, DEFAULT_CHANNEL_SIZE);
+    let supervisor_blox = Blox::<SupervisorComponents<SimRuntime>>::new(
+        SupervisorReceivers {
+            standard_receiver,
+            supervisor_receiver: supervisor_rx,
+        },
+        SupervisorExtendedState::new(SupervisorInitArgs {
+            root_standard_handle,
+            root_future: Box::new(root_blox).run(),
+        }),
+        SupervisorHandles {
+            standard_handle: supervisor_standard_handle,
+            supervisor_handle,
+        },
+    );
+    simulation.spawn(Box::new(supervisor_blox).run());
+    transitions
+}
+
+#[test]
+fn demo_finishes_with_every_seed() {
+    for seed in 0..50 {
+        let mut simulation = Simulation::new(seed);
+        let transitions = spawn_demo(&simulation);
+        simulation.run_until_quiescent();
+        let last = transitions.lock().unwrap().last().cloned();
+        assert_eq!(
+            last.map(|(_, to)| to),
+            Some(RootStates::Finished(Finished)),
+            "seed {}",
+            seed
+        );
+    }
+}
+
+#[test]
+fn same_seed_replays_same_schedule() {
+    let run = |seed| {
+        let mut simulation = Simulation::new(seed);
+        spawn_demo(&simulation);
+        simulation.run_until_quiescent();
+        simulation.schedule()
+    };
+    assert_eq!(run(3), run(3));
+}
+
+#[test]
+fn seed_picks_the_interleaving() {
+    // Each task records its id, sleeps, and records it again
+    let run = |seed| {
+        let mut simulation = Simulation::new(seed);
+        let order = Arc::new(Mutex::new(Vec::new()));
+        for id in 0..4 {
+            let order = order.clone();
+            simulation.spawn(async move {
+                order.lock().unwrap().push(id);
+                sleep(Duration::from_millis(1)).await;
+                order.lock().unwrap().push(id);
+            });
+        }
+        simulation.run_until_quiescent();
+        let order = order.lock().unwrap().clone();
+        order
+    };
+    assert_eq!(run(1), run(1));
+    assert!((2..20).any(|seed| run(seed) != run(1)));
+}
+
+#[test]
+fn virtual_clock_advances_to_timers() {
+    let mut simulation = Simulation::new(0);
+    let (handle, mut receiver) = SimMessageHandle::<u32>::create_channel_with_size(1, 4);
+    simulation.spawn(async move {
+        sleep(Duration::from_secs(5)).await;
+        handle.try_send(Message::new(0, 5)).unwrap();
+    });
+
+    simulation.run_for(Duration::from_secs(2));
+    assert_eq!(simulation.now(), Duration::from_secs(2));
+    let waker = std::task::Waker::noop();
+    let mut cx = std::task::Context::from_waker(waker);
+    let poll = futures_core::Stream::poll_next(std::pin::Pin::new(&mut receiver), &mut cx);
+    assert!(poll.is_pending());
+
+    simulation.run_until_quiescent();
+    assert_eq!(simulation.now(), Duration::from_secs(5));
+    let poll = futures_core::Stream::poll_next(std::pin::Pin::new(&mut receiver), &mut cx);
+    assert!(matches!(poll, std::task::Poll::Ready(Some(msg)) if msg.payload == 5));
+}
+
+#[test]
+fn sleep_adds_its_timer_once() {
+    let mut simulation = Simulation::new(0);
+    simulation.spawn(async {
+        let mut sleep = std::pin::pin!(sleep(Duration::from_secs(5)));
+        // Polls the sleep again on every spurious wake
+        let mut wakes = 0;
+        std::future::poll_fn(|cx| {
+            let poll = std::future::Future::poll(sleep.as_mut(), cx);
+            if poll.is_pending() && wakes < 3 {
+                wakes += 1;
+                cx.waker().wake_by_ref();
+            }
+            poll
+        })
+        .await;
+    });
+
+    assert_eq!(simulation.run_for(Duration::from_secs(2)), 4);
+    assert_eq!(simulation.pending_timers(), 1);
+    simulation.run_until_quiescent();
+    assert_eq!(simulation.now(), Duration::from_secs(5));
+    assert_eq!(simulation.pending_timers(), 0);
+}
@@ -0,0 +1,3 @@
+// Copyright 2025 Bloxide, all rights reserved
+pub mod runtime;
+pub use runtime::*;
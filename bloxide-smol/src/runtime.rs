@@ -0,0 +1,95 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+pub use async_channel::{Receiver, Sender, TrySendError};
+use bloxide_core::components::{BloxFactory, DynamicRuntime, Runtime, SpawnError, SpawnMode};
+use bloxide_core::messaging::*;
+use bloxide_core::std_exports::*;
+
+pub const DEFAULT_CHANNEL_SIZE: usize = 32;
+
+pub const STANDARD_MESSAGE_CHANNEL_SIZE: usize = DEFAULT_CHANNEL_SIZE;
+
+/// Spawns bloxes on smol's global executor.  Supports `SpawnMode::Dedicated`.
+#[derive(Clone)]
+pub struct SmolRuntime;
+
+impl Runtime for SmolRuntime {
+    type MessageHandle<P: Send + 'static> = SmolMessageHandle<P>;
+
+    fn spawn<F>(f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        smol::spawn(f).detach();
+    }
+
+    // async_channel's Receiver isn't Unpin
+    type ReceiverStream<P: Send + 'static> = Pin<Box<Receiver<Message<P>>>>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        Box::pin(receiver)
+    }
+
+    // Runs the blox on a new thread with its own LocalExecutor, until the blox's future
+    // completes.  Tasks still pending on that executor are dropped with it
+    fn spawn_with(mode: SpawnMode, factory: BloxFactory) -> Result<(), SpawnError> {
+        if mode != SpawnMode::Dedicated {
+            return Err(SpawnError::Unsupported(mode));
+        }
+        std::thread::Builder::new()
+            .name("bloxide-blox".to_string())
+            .spawn(move || {
+                let executor = smol::LocalExecutor::new();
+                smol::block_on(executor.run(factory()));
+            })
+            .map_err(|e| SpawnError::Failed(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl DynamicRuntime for SmolRuntime {}
+
+#[derive(Debug)]
+pub struct SmolMessageHandle<P: Send + 'static> {
+    id: u16,
+    sender: Sender<Message<P>>,
+}
+
+impl<P: Send + 'static> Clone for SmolMessageHandle<P> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<P: Send + 'static> MessageSender for SmolMessageHandle<P> {
+    type PayloadType = P;
+    type SenderType = Sender<Message<P>>;
+    type ReceiverType = Receiver<Message<P>>;
+    type ErrorType = TrySendError<Message<P>>;
+
+    fn try_send(&self, msg: Message<P>) -> Result<(), Self::ErrorType> {
+        self.sender.try_send(msg)
+    }
+
+    fn error_kind(error: &Self::ErrorType) -> SendErrorKind {
+        match error {
+            TrySendError::Full(_) => SendErrorKind::Full,
+            TrySendError::Closed(_) => SendErrorKind::Closed,
+        }
+    }
+
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn create_channel_with_size(id: u16, size: usize) -> (Self, Self::ReceiverType) {
+        // async_channel panics on a zero capacity
+        let (tx, rx) = async_channel::bounded(size.max(1));
+        (Self { id, sender: tx }, rx)
+    }
+}
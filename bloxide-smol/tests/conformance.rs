@@ -0,0 +1,6 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_smol::SmolRuntime;
+use smol::block_on;
+
+bloxide_core::conformance_tests!(SmolRuntime, block_on);
@@ -0,0 +1,95 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::{
+        demo_root::{components::*, ext_state::*},
+        supervisor::{components::*, ext_state::*},
+    },
+    components::*,
+    messaging::*,
+    state_machine::*,
+    std_exports::*,
+};
+use bloxide_std::{StdMessageHandle, StdRuntime, DEFAULT_CHANNEL_SIZE};
+use log::*;
+use std::thread::sleep;
+use std::time::Duration;
+
+fn main() {
+    env_logger::builder()
+        .filter_level(log::LevelFilter::Info)
+        .parse_default_env()
+        .init();
+    // Create the Supervisor handle first so it can be passed to the Root
+    let (supervisor_supervisor_handle, supervisor_supervisor_rx) =
+        StdMessageHandle::create_channel_with_size(11, DEFAULT_CHANNEL_SIZE);
+
+    let (root_standard_handle, root_standard_rx) =
+        StdMessageHandle::create_channel_with_size(1, DEFAULT_CHANNEL_SIZE);
+
+    let (root_counter_handle, root_counter_rx) =
+        StdMessageHandle::create_channel_with_size(2, DEFAULT_CHANNEL_SIZE);
+
+    let root_receivers: <RootComponents<StdRuntime> as bloxide_core::Components>::Receivers =
+        RootReceivers {
+            std_rx: root_standard_rx,
+            counter_rx: root_counter_rx,
+        };
+
+    let root_handles: <RootComponents<StdRuntime> as bloxide_core::Components>::Handles =
+        RootHandles {
+            standard_handle: root_standard_handle.clone(),
+            counter_handle: root_counter_handle,
+        };
+
+    let root_init_args = RootInitArgs {
+        supervisor_handle: supervisor_supervisor_handle.clone(),
+        counter_handle: None,
+    };
+
+    let root_extended_state = RootExtState::<StdRuntime>::new(root_init_args);
+
+    // f) Construct the Root Blox
+    let root_blox =
+        Blox::<RootComponents<StdRuntime>>::new(root_receivers, root_extended_state, root_handles);
+
+    let root_future = Box::pin(async move {
+        Box::new(root_blox).run().await;
+    });
+
+    let (supervisor_standard_handle, supervisor_standard_rx) =
+        StdMessageHandle::create_channel_with_size(10, DEFAULT_CHANNEL_SIZE);
+
+    let supervisor_receivers = SupervisorReceivers::<StdRuntime> {
+        standard_receiver: supervisor_standard_rx,
+        supervisor_receiver: supervisor_supervisor_rx,
+    };
+
+    let supervisor_handles = SupervisorHandles::<StdRuntime> {
+        standard_handle: supervisor_standard_handle,
+        supervisor_handle: supervisor_supervisor_handle,
+    };
+
+    let supervisor_init_args = SupervisorInitArgs::<StdRuntime> {
+        root_standard_handle,
+        root_future,
+    };
+
+    let supervisor_extended_state =
+        SupervisorExtendedState::<StdRuntime>::new(supervisor_init_args);
+
+    let supervisor_blox = Blox::<SupervisorComponents<StdRuntime>>::new(
+        supervisor_receivers,
+        supervisor_extended_state,
+        supervisor_handles,
+    );
+
+    StdRuntime::spawn(async move {
+        Box::new(supervisor_blox).run().await;
+        info!("Supervisor finished.");
+    });
+
+    // Wait briefly to show them doing work
+    sleep(Duration::from_secs(2));
+    info!("Main done!");
+}
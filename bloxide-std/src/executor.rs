@@ -0,0 +1,39 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::components::*;
+use bloxide_core::std_exports::*;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+/// Runs a future to completion on the current thread, parking the thread while the
+/// future is pending
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = core::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+/// Runs a blox on the current thread until its run loop ends, the blocking
+/// alternative to spawning `Runnable::run`
+pub fn run_blocking<C, B>(blox: Box<B>)
+where
+    C: Components,
+    B: Runnable<C> + ?Sized,
+{
+    block_on(blox.run());
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
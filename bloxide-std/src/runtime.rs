@@ -0,0 +1,173 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::executor::block_on;
+use bloxide_core::components::{BloxFactory, DynamicRuntime, Runtime, SpawnError, SpawnMode};
+use bloxide_core::messaging::*;
+use bloxide_core::std_exports::*;
+use futures_core::Stream;
+use log::*;
+use std::sync::mpsc::{self, RecvError, SyncSender, TryRecvError, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+pub const DEFAULT_CHANNEL_SIZE: usize = 32;
+
+pub const STANDARD_MESSAGE_CHANNEL_SIZE: usize = DEFAULT_CHANNEL_SIZE;
+
+/// Runs every spawned blox on its own OS thread, no async executor needed.
+/// Channels are bounded `std::sync::mpsc` channels.
+#[derive(Clone)]
+pub struct StdRuntime;
+
+impl Runtime for StdRuntime {
+    type MessageHandle<P: Send + 'static> = StdMessageHandle<P>;
+
+    fn spawn<F>(f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if let Err(e) = std::thread::Builder::new()
+            .name("bloxide-blox".to_string())
+            .spawn(move || block_on(f))
+        {
+            error!("Failed to spawn blox thread: {:?}", e);
+        }
+    }
+
+    type ReceiverStream<P: Send + 'static> = StdReceiver<P>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        receiver
+    }
+
+    // Every blox already has its own thread, so only `Dedicated` is supported
+    fn spawn_with(mode: SpawnMode, factory: BloxFactory) -> Result<(), SpawnError> {
+        if mode != SpawnMode::Dedicated {
+            return Err(SpawnError::Unsupported(mode));
+        }
+        std::thread::Builder::new()
+            .name("bloxide-blox".to_string())
+            .spawn(move || block_on(factory()))
+            .map_err(|e| SpawnError::Failed(e.to_string()))?;
+        Ok(())
+    }
+}
+
+impl DynamicRuntime for StdRuntime {}
+
+// Waker of a receiver polled as a stream, woken by every send and every dropped handle
+type WakerSlot = Arc<Mutex<Option<Waker>>>;
+
+fn wake(slot: &WakerSlot) {
+    if let Some(waker) = slot.lock().unwrap().take() {
+        waker.wake();
+    }
+}
+
+pub struct StdMessageHandle<P: Send + 'static> {
+    id: u16,
+    // Only `None` while the handle is dropped
+    sender: Option<SyncSender<Message<P>>>,
+    waker: WakerSlot,
+}
+
+impl<P: Send + 'static> Clone for StdMessageHandle<P> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            sender: self.sender.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<P: Send + 'static> Drop for StdMessageHandle<P> {
+    // Lets a receiver stream notice it may have lost its last sender, the sender is
+    // dropped first so the receiver sees it gone when it wakes
+    fn drop(&mut self) {
+        self.sender.take();
+        wake(&self.waker);
+    }
+}
+
+impl<P: Send + 'static> fmt::Debug for StdMessageHandle<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StdMessageHandle({})", self.id)
+    }
+}
+
+impl<P: Send + 'static> MessageSender for StdMessageHandle<P> {
+    type PayloadType = P;
+    type SenderType = SyncSender<Message<P>>;
+    type ReceiverType = StdReceiver<P>;
+    type ErrorType = TrySendError<Message<P>>;
+
+    fn try_send(&self, msg: Message<P>) -> Result<(), Self::ErrorType> {
+        if let Some(sender) = &self.sender {
+            sender.try_send(msg)?;
+        }
+        wake(&self.waker);
+        Ok(())
+    }
+
+    fn error_kind(error: &Self::ErrorType) -> SendErrorKind {
+        match error {
+            TrySendError::Full(_) => SendErrorKind::Full,
+            TrySendError::Disconnected(_) => SendErrorKind::Closed,
+        }
+    }
+
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn create_channel_with_size(id: u16, size: usize) -> (Self, Self::ReceiverType) {
+        // A zero sized sync_channel is a rendezvous channel, try_send would only
+        // succeed while the receiver is blocked in recv
+        let (sender, receiver) = mpsc::sync_channel(size.max(1));
+        let waker = WakerSlot::default();
+        (
+            Self {
+                id,
+                sender: Some(sender),
+                waker: waker.clone(),
+            },
+            StdReceiver { receiver, waker },
+        )
+    }
+}
+
+/// Receiving side of a `StdMessageHandle` channel.  Can be used as a `Stream` by a
+/// blox, or read with the blocking `recv` from synchronous code.
+pub struct StdReceiver<P: Send + 'static> {
+    receiver: mpsc::Receiver<Message<P>>,
+    waker: WakerSlot,
+}
+
+impl<P: Send + 'static> StdReceiver<P> {
+    /// Blocks until a message arrives, errors once every handle has been dropped
+    pub fn recv(&self) -> Result<Message<P>, RecvError> {
+        self.receiver.recv()
+    }
+
+    pub fn try_recv(&self) -> Result<Message<P>, TryRecvError> {
+        self.receiver.try_recv()
+    }
+}
+
+impl<P: Send + 'static> Stream for StdReceiver<P> {
+    type Item = Message<P>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Register before checking so a send between the check and the registration
+        // isn't missed
+        *self.waker.lock().unwrap() = Some(cx.waker().clone());
+        match self.receiver.try_recv() {
+            Ok(msg) => Poll::Ready(Some(msg)),
+            Err(TryRecvError::Empty) => Poll::Pending,
+            Err(TryRecvError::Disconnected) => Poll::Ready(None),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_std::{block_on, StdRuntime};
+
+bloxide_core::conformance_tests!(StdRuntime, block_on);
@@ -0,0 +1,64 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use super::{ext_state::*, messaging::*, states::*};
+use crate::{TokioMessageHandle, TokioRuntime};
+use bloxide_core::{components::*, merge::*, messaging::*, spans::in_blox_span, std_exports::*};
+use log::*;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+
+pub struct GatewayComponents;
+
+impl Components for GatewayComponents {
+    type States = GatewayStateEnum;
+    type MessageSet = GatewayMessageSet;
+    type ExtendedState = GatewayExtendedState;
+    type Receivers = GatewayReceivers;
+    type Handles = GatewayHandles;
+}
+
+#[derive(Clone)]
+pub struct GatewayHandles {
+    pub standard_handle: TokioMessageHandle<StandardPayload<TokioRuntime>>,
+    pub gateway_handle: TokioMessageHandle<GatewayPayload>,
+}
+
+pub struct GatewayReceivers {
+    pub standard_receiver: mpsc::Receiver<Message<StandardPayload<TokioRuntime>>>,
+    pub gateway_receiver: mpsc::Receiver<Message<GatewayPayload>>,
+}
+
+impl Runnable<GatewayComponents> for Blox<GatewayComponents> {
+    fn run(mut self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
+        let id = self.state_machine.self_handles.standard_handle.id();
+        Box::pin(in_blox_span("gateway", id, async move {
+            self.state_machine.init(
+                &GatewayStateEnum::Uninit(Uninit),
+                &GatewayStateEnum::Connected(Connected),
+            );
+
+            let standard_stream = TokioRuntime::to_stream(self.receivers.standard_receiver);
+            let gateway_stream = TokioRuntime::to_stream(self.receivers.gateway_receiver);
+
+            let mut merged = MergedStream2::new(standard_stream, gateway_stream);
+
+            while !self.state_machine.is_finished() {
+                let Some(item) = merged.next().await else {
+                    break;
+                };
+                match item {
+                    MergedItem::From1(std_msg) => {
+                        let msg = GatewayMessageSet::StandardMessage(std_msg);
+                        self.state_machine.dispatch(msg);
+                    }
+                    MergedItem::From2(gateway_msg) => {
+                        let msg = GatewayMessageSet::GatewayMessage(gateway_msg);
+                        self.state_machine.dispatch(msg);
+                    }
+                }
+            }
+
+            trace!("Gateway run loop complete.");
+        }))
+    }
+}
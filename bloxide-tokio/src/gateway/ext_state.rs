@@ -0,0 +1,89 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use super::messaging::*;
+use bloxide_core::{messaging::*, state_machine::*, std_exports::*};
+use log::*;
+use std::collections::HashMap;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::task::AbortHandle;
+
+pub struct GatewayExtendedState {
+    /// Local bloxes reachable from the remote side, by id
+    pub routes: HashMap<u16, InboundRoute>,
+    /// Frames for the socket's writer task, dropped once the gateway closes
+    pub writer: Option<mpsc::Sender<RawPayload>>,
+    /// The socket's reader task, aborted once the gateway closes
+    pub reader: Option<AbortHandle>,
+}
+
+pub struct GatewayInitArgs {
+    pub writer: mpsc::Sender<RawPayload>,
+    pub reader: AbortHandle,
+    pub routes: Vec<(u16, InboundRoute)>,
+}
+
+impl GatewayExtendedState {
+    /// Queues `raw` to be written to the socket.  If the writer is behind and its queue
+    /// is full the frame is dropped, an error means the writer has stopped.
+    pub fn write(&self, raw: RawPayload) -> Result<(), StateError> {
+        let writer = self
+            .writer
+            .as_ref()
+            .ok_or(StateError::MissingHandle("writer"))?;
+        match writer.try_send(raw) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(raw)) => {
+                warn!(
+                    "Socket writer is behind, dropping message from {} to remote {}",
+                    raw.from, raw.to
+                );
+                Ok(())
+            }
+            Err(TrySendError::Closed(_)) => Err(StateError::other("Socket writer has stopped")),
+        }
+    }
+
+    /// Stops the writer task, which shuts the socket down, and the reader task, which
+    /// would otherwise wait for the remote side to close
+    pub fn close(&mut self) {
+        self.writer = None;
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
+    }
+
+    /// Delivers a frame read from the socket to its local blox.  Frames for unknown
+    /// bloxes and frames that can't be delivered are dropped, as the sender is remote.
+    pub fn deliver(&self, raw: RawPayload) {
+        let (to, from) = (raw.to, raw.from);
+        match self.routes.get(&to) {
+            Some(route) => {
+                if let Err(e) = route(raw) {
+                    warn!("Dropping message from remote {} to {}: {}", from, to, e);
+                }
+            }
+            None => warn!(
+                "Dropping message from remote {} to unknown blox {}",
+                from, to
+            ),
+        }
+    }
+}
+
+impl ExtendedState for GatewayExtendedState {
+    type InitArgs = GatewayInitArgs;
+
+    fn new(args: Self::InitArgs) -> Self {
+        GatewayExtendedState {
+            routes: args.routes.into_iter().collect(),
+            writer: Some(args.writer),
+            reader: Some(args.reader),
+        }
+    }
+}
+
+impl fmt::Debug for GatewayExtendedState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "GatewayExtendedState")
+    }
+}
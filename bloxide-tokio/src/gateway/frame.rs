@@ -0,0 +1,50 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Framing of `RawPayload`s on a byte stream.
+//!
+//! A frame is its length, `u32` little-endian, then `to` and `from`, `u16` little-endian,
+//! then the payload bytes.  The length counts everything after itself.
+
+use bloxide_core::messaging::RawPayload;
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Longest frame accepted, longer ones are rejected as invalid data
+pub const MAX_FRAME_LEN: usize = 1 << 24;
+
+// `to` and `from`
+const HEADER_LEN: usize = 4;
+
+pub fn encode_frame(raw: &RawPayload) -> Vec<u8> {
+    let len = (HEADER_LEN + raw.payload.len()) as u32;
+    let mut frame = Vec::with_capacity(4 + len as usize);
+    frame.extend_from_slice(&len.to_le_bytes());
+    frame.extend_from_slice(&raw.to.to_le_bytes());
+    frame.extend_from_slice(&raw.from.to_le_bytes());
+    frame.extend_from_slice(&raw.payload);
+    frame
+}
+
+/// Reads the next frame, `None` once the stream ends between frames
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Option<RawPayload>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_le_bytes(len) as usize;
+    if !(HEADER_LEN..=MAX_FRAME_LEN).contains(&len) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Invalid frame length {}", len),
+        ));
+    }
+    let mut frame = vec![0u8; len];
+    reader.read_exact(&mut frame).await?;
+    Ok(Some(RawPayload {
+        to: u16::from_le_bytes([frame[0], frame[1]]),
+        from: u16::from_le_bytes([frame[2], frame[3]]),
+        payload: frame.split_off(HEADER_LEN),
+    }))
+}
@@ -0,0 +1,72 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::TokioRuntime;
+use bloxide_core::{messaging::*, std_exports::*};
+
+/// Delivers a frame read from the socket to a local blox
+pub type InboundRoute = Box<dyn Fn(RawPayload) -> Result<(), String> + Send>;
+
+pub enum GatewayMessageSet {
+    StandardMessage(Message<StandardPayload<TokioRuntime>>),
+    GatewayMessage(Message<GatewayPayload>),
+}
+
+impl MessageSet for GatewayMessageSet {
+    fn source_id(&self) -> u16 {
+        match self {
+            GatewayMessageSet::StandardMessage(msg) => msg.source_id(),
+            GatewayMessageSet::GatewayMessage(msg) => msg.source_id(),
+        }
+    }
+}
+
+impl fmt::Debug for GatewayMessageSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GatewayMessageSet::StandardMessage(msg) => write!(
+                f,
+                "StandardMessage from {}: {:?}",
+                msg.source_id, msg.payload
+            ),
+            GatewayMessageSet::GatewayMessage(msg) => write!(
+                f,
+                "GatewayMessage from {}: {:?}",
+                msg.source_id, msg.payload
+            ),
+        }
+    }
+}
+
+pub enum GatewayPayload {
+    /// A message for a remote blox, written to the socket
+    Outbound(RawPayload),
+    /// A frame read from the socket, for the local blox `to`
+    Inbound(RawPayload),
+    /// Delivers inbound frames for the local blox `id` with the route
+    Expose(u16, InboundRoute),
+    /// The connection ended, with the error if it failed
+    Closed(Option<String>),
+}
+
+impl fmt::Debug for GatewayPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GatewayPayload::Outbound(raw) => write!(f, "Outbound to {}", raw.to),
+            GatewayPayload::Inbound(raw) => write!(f, "Inbound for {}", raw.to),
+            GatewayPayload::Expose(id, _) => write!(f, "Expose: {}", id),
+            GatewayPayload::Closed(error) => write!(f, "Closed: {:?}", error),
+        }
+    }
+}
+
+impl From<Message<StandardPayload<TokioRuntime>>> for GatewayMessageSet {
+    fn from(msg: Message<StandardPayload<TokioRuntime>>) -> Self {
+        GatewayMessageSet::StandardMessage(msg)
+    }
+}
+
+impl From<Message<GatewayPayload>> for GatewayMessageSet {
+    fn from(msg: Message<GatewayPayload>) -> Self {
+        GatewayMessageSet::GatewayMessage(msg)
+    }
+}
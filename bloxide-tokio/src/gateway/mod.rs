@@ -0,0 +1,210 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Gateway blox bridging bloxes across processes over a TCP or Unix domain socket.
+//!
+//! Each end of a connection runs a gateway.  A local blox sends to a remote one through
+//! a proxy handle from `proxy`, an ordinary `TokioMessageHandle` with the remote blox's
+//! id, so the sender can't tell the blox is remote.  Messages sent on it are encoded
+//! with a `Codec` into `RawPayload`s, framed, see `frame`, and written to the socket.
+//! Frames wait for the socket in a bounded queue, and are dropped while it is full.
+//! The gateway at the other end delivers each frame it reads to the local blox it is
+//! addressed to, through the routes it was given with `route` or `raw_route`, or
+//! exposed later with `GatewayPayload::Expose`.  Frames for other bloxes are dropped.
+//!
+//! Bloxes can also send `StandardPayload::RawOutbound(to, bytes)` to the gateway's
+//! standard handle, and receive `StandardPayload::RawInbound(from, bytes)` through a
+//! `raw_route`.
+
+pub mod components;
+pub mod ext_state;
+pub mod frame;
+pub mod messaging;
+pub mod states;
+
+pub use components::*;
+pub use ext_state::*;
+pub use messaging::*;
+
+use crate::{TokioMessageHandle, TokioRuntime, DEFAULT_CHANNEL_SIZE};
+use bloxide_core::{codec::*, components::*, messaging::*, state_machine::*};
+use log::*;
+use std::io;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+
+/// Route delivering messages for `handle`'s blox, decoded with `C` as `P`
+pub fn route<P, C>(handle: TokioMessageHandle<P>) -> (u16, InboundRoute)
+where
+    P: WirePayload + Send + 'static,
+    C: Codec,
+{
+    let id = handle.id();
+    let route = move |raw: RawPayload| {
+        let message = Message::<P>::from_raw::<C>(&raw).map_err(|e| e.to_string())?;
+        handle.try_send(message).map_err(|e| e.to_string())
+    };
+    (id, Box::new(route))
+}
+
+/// Route delivering messages for `handle`'s blox undecoded, as
+/// `StandardPayload::RawInbound`
+pub fn raw_route(handle: TokioMessageHandle<StandardPayload<TokioRuntime>>) -> (u16, InboundRoute) {
+    let id = handle.id();
+    let route = move |raw: RawPayload| {
+        let message = Message::new(raw.from, StandardPayload::RawInbound(raw.from, raw.payload));
+        handle.try_send(message).map_err(|e| e.to_string())
+    };
+    (id, Box::new(route))
+}
+
+/// Handle for the remote blox `remote_id`.  Messages sent on it are encoded with `C` and
+/// sent through `gateway`, waiting for room in the gateway's channel.  Messages that
+/// can't be encoded are dropped.
+pub fn proxy<P, C>(
+    gateway: &TokioMessageHandle<GatewayPayload>,
+    remote_id: u16,
+) -> TokioMessageHandle<P>
+where
+    P: WirePayload + Send + 'static,
+    C: Codec,
+{
+    let (handle, mut receiver) =
+        TokioMessageHandle::<P>::create_channel_with_size(remote_id, DEFAULT_CHANNEL_SIZE);
+    let gateway = gateway.clone();
+    tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            let raw = match message.to_raw::<C>(remote_id) {
+                Ok(raw) => raw,
+                Err(e) => {
+                    error!("Failed to encode message for remote {}: {}", remote_id, e);
+                    continue;
+                }
+            };
+            let outbound = Message::new(message.source_id, GatewayPayload::Outbound(raw));
+            if gateway.sender().send(outbound).await.is_err() {
+                break;
+            }
+        }
+    });
+    handle
+}
+
+/// Spawns a gateway with id `id` on the connection `stream`, delivering inbound messages
+/// through `routes`.  Both of the returned handles have the id `id`.
+pub fn spawn_gateway<S>(
+    id: u16,
+    stream: S,
+    routes: impl IntoIterator<Item = (u16, InboundRoute)>,
+) -> GatewayHandles
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (standard_handle, standard_receiver) =
+        TokioMessageHandle::create_channel_with_size(id, DEFAULT_CHANNEL_SIZE);
+    let (gateway_handle, gateway_receiver) =
+        TokioMessageHandle::create_channel_with_size(id, DEFAULT_CHANNEL_SIZE);
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    let (frames, mut frames_receiver) = mpsc::channel::<RawPayload>(DEFAULT_CHANNEL_SIZE);
+
+    let inbound: TokioMessageHandle<GatewayPayload> = gateway_handle.clone();
+    let reader_task = tokio::spawn(async move {
+        let closed = loop {
+            match frame::read_frame(&mut reader).await {
+                Ok(Some(raw)) => {
+                    let message = Message::new(id, GatewayPayload::Inbound(raw));
+                    if inbound.sender().send(message).await.is_err() {
+                        return;
+                    }
+                }
+                Ok(None) => break None,
+                Err(e) => break Some(e.to_string()),
+            }
+        };
+        let _ = inbound
+            .sender()
+            .send(Message::new(id, GatewayPayload::Closed(closed)))
+            .await;
+    });
+
+    let outbound = gateway_handle.clone();
+    tokio::spawn(async move {
+        while let Some(raw) = frames_receiver.recv().await {
+            if let Err(e) = writer.write_all(&frame::encode_frame(&raw)).await {
+                let _ = outbound.try_send(Message::new(
+                    id,
+                    GatewayPayload::Closed(Some(e.to_string())),
+                ));
+                return;
+            }
+        }
+        // The gateway closed
+        let _ = writer.shutdown().await;
+    });
+
+    let handles = GatewayHandles {
+        standard_handle,
+        gateway_handle,
+    };
+    let blox = Blox::<GatewayComponents>::new(
+        GatewayReceivers {
+            standard_receiver,
+            gateway_receiver,
+        },
+        GatewayExtendedState::new(GatewayInitArgs {
+            writer: frames,
+            reader: reader_task.abort_handle(),
+            routes: routes.into_iter().collect(),
+        }),
+        handles.clone(),
+    );
+    TokioRuntime::spawn(Box::new(blox).run());
+    handles
+}
+
+/// Connects to `addr` and spawns a gateway on the connection
+pub async fn connect_tcp(
+    id: u16,
+    addr: impl ToSocketAddrs,
+    routes: impl IntoIterator<Item = (u16, InboundRoute)>,
+) -> io::Result<GatewayHandles> {
+    let stream = TcpStream::connect(addr).await?;
+    stream.set_nodelay(true)?;
+    Ok(spawn_gateway(id, stream, routes))
+}
+
+/// Accepts one connection on `listener` and spawns a gateway on it
+pub async fn accept_tcp(
+    id: u16,
+    listener: &TcpListener,
+    routes: impl IntoIterator<Item = (u16, InboundRoute)>,
+) -> io::Result<GatewayHandles> {
+    let (stream, peer) = listener.accept().await?;
+    info!("Gateway {} accepted {}", id, peer);
+    stream.set_nodelay(true)?;
+    Ok(spawn_gateway(id, stream, routes))
+}
+
+/// Connects to the socket at `path` and spawns a gateway on the connection
+#[cfg(unix)]
+pub async fn connect_unix(
+    id: u16,
+    path: impl AsRef<std::path::Path>,
+    routes: impl IntoIterator<Item = (u16, InboundRoute)>,
+) -> io::Result<GatewayHandles> {
+    let stream = UnixStream::connect(path).await?;
+    Ok(spawn_gateway(id, stream, routes))
+}
+
+/// Accepts one connection on `listener` and spawns a gateway on it
+#[cfg(unix)]
+pub async fn accept_unix(
+    id: u16,
+    listener: &UnixListener,
+    routes: impl IntoIterator<Item = (u16, InboundRoute)>,
+) -> io::Result<GatewayHandles> {
+    let (stream, _) = listener.accept().await?;
+    Ok(spawn_gateway(id, stream, routes))
+}
@@ -0,0 +1,32 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use super::*;
+use log::*;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Closed;
+
+impl State<GatewayComponents> for Closed {
+    fn parent(&self) -> GatewayStateEnum {
+        GatewayStateEnum::Uninit(Uninit)
+    }
+
+    // The connection is gone, the gateway's run loop exits
+    fn is_final(&self) -> bool {
+        true
+    }
+
+    fn handle_message(
+        &self,
+        _state_machine: &mut StateMachine<GatewayComponents>,
+        _message: GatewayMessageSet,
+    ) -> Option<Transition<GatewayStateEnum, GatewayMessageSet>> {
+        None
+    }
+
+    fn on_entry(&self, state_machine: &mut StateMachine<GatewayComponents>) {
+        trace!("State on_entry: {:?}", self);
+        state_machine.extended_state.close();
+        info!("Gateway closed");
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use super::*;
+use bloxide_core::messaging::*;
+use log::*;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Connected;
+
+impl State<GatewayComponents> for Connected {
+    fn parent(&self) -> GatewayStateEnum {
+        GatewayStateEnum::Uninit(Uninit)
+    }
+
+    fn try_handle_message(
+        &self,
+        state_machine: &mut StateMachine<GatewayComponents>,
+        message: GatewayMessageSet,
+    ) -> HandlerResult<GatewayStateEnum, GatewayMessageSet> {
+        let outbound = match message {
+            GatewayMessageSet::GatewayMessage(message) => match message.payload {
+                GatewayPayload::Outbound(raw) => raw,
+                GatewayPayload::Inbound(raw) => {
+                    state_machine.extended_state.deliver(raw);
+                    return Ok(None);
+                }
+                GatewayPayload::Expose(id, route) => {
+                    state_machine.extended_state.routes.insert(id, route);
+                    return Ok(None);
+                }
+                GatewayPayload::Closed(error) => {
+                    match error {
+                        Some(e) => warn!("Gateway connection failed: {}", e),
+                        None => info!("Gateway connection closed by the remote side"),
+                    }
+                    return Ok(Some(Transition::To(GatewayStateEnum::Closed(Closed))));
+                }
+            },
+            GatewayMessageSet::StandardMessage(message) => match message.payload {
+                StandardPayload::Shutdown => {
+                    return Ok(Some(Transition::To(GatewayStateEnum::Closed(Closed))));
+                }
+                StandardPayload::RawOutbound(to, payload) => RawPayload {
+                    to,
+                    from: message.source_id,
+                    payload,
+                },
+                _ => return Ok(None),
+            },
+        };
+        if let Err(e) = state_machine.extended_state.write(outbound) {
+            warn!("{}, closing the gateway", e);
+            return Ok(Some(Transition::To(GatewayStateEnum::Closed(Closed))));
+        }
+        Ok(None)
+    }
+}
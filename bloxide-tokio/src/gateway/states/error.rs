@@ -0,0 +1,32 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use super::*;
+use log::*;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Error;
+
+impl State<GatewayComponents> for Error {
+    fn parent(&self) -> GatewayStateEnum {
+        GatewayStateEnum::Uninit(Uninit)
+    }
+
+    // A failed gateway closes its connection and its run loop exits, as in `Closed`
+    fn is_final(&self) -> bool {
+        true
+    }
+
+    fn handle_message(
+        &self,
+        _state_machine: &mut StateMachine<GatewayComponents>,
+        _message: GatewayMessageSet,
+    ) -> Option<Transition<GatewayStateEnum, GatewayMessageSet>> {
+        None
+    }
+
+    fn on_entry(&self, state_machine: &mut StateMachine<GatewayComponents>) {
+        trace!("State on_entry: {:?}", self);
+        state_machine.extended_state.close();
+        error!("Gateway failed: {:?}, closing", state_machine.last_error());
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+pub mod closed;
+pub mod connected;
+pub mod error;
+pub mod uninit;
+
+use super::{components::*, messaging::*};
+use bloxide_core::state_machine::*;
+pub use {closed::*, connected::*, error::*, uninit::*};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum GatewayStateEnum {
+    Uninit(Uninit),
+    Connected(Connected),
+    Closed(Closed),
+    Error(Error),
+}
+
+impl Default for GatewayStateEnum {
+    fn default() -> Self {
+        GatewayStateEnum::Uninit(Uninit)
+    }
+}
+
+impl StateEnum for GatewayStateEnum {
+    fn all_states() -> &'static [Self] {
+        &[
+            GatewayStateEnum::Uninit(Uninit),
+            GatewayStateEnum::Connected(Connected),
+            GatewayStateEnum::Closed(Closed),
+            GatewayStateEnum::Error(Error),
+        ]
+    }
+
+    fn transitions() -> &'static [(Self, Self)] {
+        &[(
+            GatewayStateEnum::Connected(Connected),
+            GatewayStateEnum::Closed(Closed),
+        )]
+    }
+
+    fn error_state() -> Option<Self> {
+        Some(GatewayStateEnum::Error(Error))
+    }
+}
+
+impl State<GatewayComponents> for GatewayStateEnum {
+    fn on_entry(&self, state_machine: &mut StateMachine<GatewayComponents>) {
+        match self {
+            GatewayStateEnum::Uninit(s) => s.on_entry(state_machine),
+            GatewayStateEnum::Connected(s) => s.on_entry(state_machine),
+            GatewayStateEnum::Closed(s) => s.on_entry(state_machine),
+            GatewayStateEnum::Error(s) => s.on_entry(state_machine),
+        }
+    }
+
+    fn on_exit(&self, state_machine: &mut StateMachine<GatewayComponents>) {
+        match self {
+            GatewayStateEnum::Uninit(s) => s.on_exit(state_machine),
+            GatewayStateEnum::Connected(s) => s.on_exit(state_machine),
+            GatewayStateEnum::Closed(s) => s.on_exit(state_machine),
+            GatewayStateEnum::Error(s) => s.on_exit(state_machine),
+        }
+    }
+
+    fn handle_message(
+        &self,
+        state_machine: &mut StateMachine<GatewayComponents>,
+        message: GatewayMessageSet,
+    ) -> Option<Transition<GatewayStateEnum, GatewayMessageSet>> {
+        match self {
+            GatewayStateEnum::Uninit(s) => s.handle_message(state_machine, message),
+            GatewayStateEnum::Connected(s) => s.handle_message(state_machine, message),
+            GatewayStateEnum::Closed(s) => s.handle_message(state_machine, message),
+            GatewayStateEnum::Error(s) => s.handle_message(state_machine, message),
+        }
+    }
+
+    fn try_on_entry(
+        &self,
+        state_machine: &mut StateMachine<GatewayComponents>,
+    ) -> Result<(), StateError> {
+        match self {
+            GatewayStateEnum::Uninit(s) => s.try_on_entry(state_machine),
+            GatewayStateEnum::Connected(s) => s.try_on_entry(state_machine),
+            GatewayStateEnum::Closed(s) => s.try_on_entry(state_machine),
+            GatewayStateEnum::Error(s) => s.try_on_entry(state_machine),
+        }
+    }
+
+    fn try_on_exit(
+        &self,
+        state_machine: &mut StateMachine<GatewayComponents>,
+    ) -> Result<(), StateError> {
+        match self {
+            GatewayStateEnum::Uninit(s) => s.try_on_exit(state_machine),
+            GatewayStateEnum::Connected(s) => s.try_on_exit(state_machine),
+            GatewayStateEnum::Closed(s) => s.try_on_exit(state_machine),
+            GatewayStateEnum::Error(s) => s.try_on_exit(state_machine),
+        }
+    }
+
+    fn try_handle_message(
+        &self,
+        state_machine: &mut StateMachine<GatewayComponents>,
+        message: GatewayMessageSet,
+    ) -> HandlerResult<GatewayStateEnum, GatewayMessageSet> {
+        match self {
+            GatewayStateEnum::Uninit(s) => s.try_handle_message(state_machine, message),
+            GatewayStateEnum::Connected(s) => s.try_handle_message(state_machine, message),
+            GatewayStateEnum::Closed(s) => s.try_handle_message(state_machine, message),
+            GatewayStateEnum::Error(s) => s.try_handle_message(state_machine, message),
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        match self {
+            GatewayStateEnum::Uninit(s) => <Uninit as State<GatewayComponents>>::is_final(s),
+            GatewayStateEnum::Connected(s) => <Connected as State<GatewayComponents>>::is_final(s),
+            GatewayStateEnum::Closed(s) => <Closed as State<GatewayComponents>>::is_final(s),
+            GatewayStateEnum::Error(s) => <Error as State<GatewayComponents>>::is_final(s),
+        }
+    }
+
+    fn parent(&self) -> GatewayStateEnum {
+        match self {
+            GatewayStateEnum::Uninit(s) => <Uninit as State<GatewayComponents>>::parent(s),
+            GatewayStateEnum::Connected(s) => <Connected as State<GatewayComponents>>::parent(s),
+            GatewayStateEnum::Closed(s) => <Closed as State<GatewayComponents>>::parent(s),
+            GatewayStateEnum::Error(s) => <Error as State<GatewayComponents>>::parent(s),
+        }
+    }
+}
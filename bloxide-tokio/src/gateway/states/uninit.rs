@@ -0,0 +1,22 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use super::*;
+use log::*;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Uninit;
+
+impl State<GatewayComponents> for Uninit {
+    fn parent(&self) -> GatewayStateEnum {
+        GatewayStateEnum::Uninit(Uninit)
+    }
+
+    fn handle_message(
+        &self,
+        _state_machine: &mut StateMachine<GatewayComponents>,
+        _message: GatewayMessageSet,
+    ) -> Option<Transition<GatewayStateEnum, GatewayMessageSet>> {
+        trace!("Uninit handle message");
+        None
+    }
+}
@@ -1,3 +1,4 @@
 // Copyright 2025 Bloxide, all rights reserved
+pub mod gateway;
 pub mod runtime;
 pub use runtime::*;
@@ -9,9 +9,11 @@ pub const DEFAULT_CHANNEL_SIZE: usize = 32;
 
 pub const STANDARD_MESSAGE_CHANNEL_SIZE: usize = DEFAULT_CHANNEL_SIZE;
 
-use bloxide_core::components::Runtime;
+use bloxide_core::components::{BloxFactory, DynamicRuntime, Runtime, SpawnError, SpawnMode};
 use tokio_stream::wrappers::ReceiverStream;
 
+/// Spawns bloxes with `tokio::spawn`.  Supports `SpawnMode::Dedicated`, for
+/// `SpawnMode::Local` use `TokioLocalRuntime`.
 #[derive(Clone)]
 pub struct TokioRuntime;
 
@@ -32,6 +34,69 @@ impl Runtime for TokioRuntime {
     ) -> Self::ReceiverStream<P> {
         ReceiverStream::new(receiver)
     }
+
+    fn spawn_with(mode: SpawnMode, factory: BloxFactory) -> Result<(), SpawnError> {
+        match mode {
+            SpawnMode::Local => Err(SpawnError::Unsupported(mode)),
+            SpawnMode::Dedicated => spawn_dedicated(factory),
+        }
+    }
+}
+
+impl DynamicRuntime for TokioRuntime {}
+
+/// Spawns bloxes with `tokio::task::spawn_local`, so every blox must be run from
+/// within a `tokio::task::LocalSet`.  Supports both spawn modes.
+#[derive(Clone)]
+pub struct TokioLocalRuntime;
+
+impl Runtime for TokioLocalRuntime {
+    type MessageHandle<P: Send + 'static> = TokioMessageHandle<P>;
+
+    fn spawn<F>(f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::task::spawn_local(f);
+    }
+
+    type ReceiverStream<P: Send + 'static> = ReceiverStream<Message<P>>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        ReceiverStream::new(receiver)
+    }
+
+    fn spawn_with(mode: SpawnMode, factory: BloxFactory) -> Result<(), SpawnError> {
+        match mode {
+            SpawnMode::Local => {
+                tokio::task::spawn_local(factory());
+                Ok(())
+            }
+            SpawnMode::Dedicated => spawn_dedicated(factory),
+        }
+    }
+}
+
+impl DynamicRuntime for TokioLocalRuntime {}
+
+// Runs the blox on a new thread with a current-thread runtime and a LocalSet, so it can
+// use `TokioLocalRuntime` too.  The thread ends once every task spawned on it is done
+fn spawn_dedicated(factory: BloxFactory) -> Result<(), SpawnError> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| SpawnError::Failed(e.to_string()))?;
+    std::thread::Builder::new()
+        .name("bloxide-blox".to_string())
+        .spawn(move || {
+            let local = tokio::task::LocalSet::new();
+            local.spawn_local(factory());
+            runtime.block_on(local);
+        })
+        .map_err(|e| SpawnError::Failed(e.to_string()))?;
+    Ok(())
 }
 
 #[derive(Debug)]
@@ -40,6 +105,13 @@ pub struct TokioMessageHandle<P: Send + 'static> {
     sender: mpsc::Sender<Message<P>>,
 }
 
+impl<P: Send + 'static> TokioMessageHandle<P> {
+    /// The channel's sender, for tasks that should wait for room rather than fail
+    pub fn sender(&self) -> &mpsc::Sender<Message<P>> {
+        &self.sender
+    }
+}
+
 impl<P: Send + 'static> Clone for TokioMessageHandle<P> {
     fn clone(&self) -> Self {
         Self {
@@ -59,6 +131,13 @@ impl<P: Send + 'static> MessageSender for TokioMessageHandle<P> {
         self.sender.try_send(msg)
     }
 
+    fn error_kind(error: &Self::ErrorType) -> SendErrorKind {
+        match error {
+            mpsc::error::TrySendError::Full(_) => SendErrorKind::Full,
+            mpsc::error::TrySendError::Closed(_) => SendErrorKind::Closed,
+        }
+    }
+
     fn id(&self) -> u16 {
         self.id
     }
@@ -0,0 +1,48 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+mod tokio_runtime {
+    use bloxide_tokio::TokioRuntime;
+    use std::future::Future;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    bloxide_core::conformance_tests!(TokioRuntime, block_on);
+}
+
+mod tokio_local_runtime {
+    use bloxide_tokio::TokioLocalRuntime;
+    use std::future::Future;
+
+    // Bloxes spawned on the local runtime need a LocalSet
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(&runtime, future)
+    }
+
+    bloxide_core::conformance_tests!(TokioLocalRuntime, block_on);
+}
+
+mod metered_tokio_runtime {
+    use bloxide_core::metrics::Metered;
+    use bloxide_tokio::TokioRuntime;
+    use std::future::Future;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    bloxide_core::conformance_tests!(Metered<TokioRuntime>, block_on);
+}
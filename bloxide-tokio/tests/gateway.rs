@@ -0,0 +1,309 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::demo_counter::messaging::*, codec::Postcard, messaging::*, state_machine::*, testing::*,
+};
+use bloxide_tokio::{
+    gateway::{states::*, *},
+    TokioMessageHandle,
+};
+use std::{process::Command, time::Duration};
+use tokio::{io::AsyncWriteExt, net::TcpListener, sync::mpsc, time::timeout};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// Set for the child process of `two_processes_over_tcp`, to the parent's address
+const PEER_ENV: &str = "BLOXIDE_GATEWAY_PEER";
+
+fn counter_channel(
+    id: u16,
+) -> (
+    TokioMessageHandle<CounterPayload>,
+    mpsc::Receiver<Message<CounterPayload>>,
+) {
+    TokioMessageHandle::create_channel_with_size(id, 8)
+}
+
+#[tokio::test]
+async fn frame_round_trip() {
+    let raw = RawPayload {
+        to: 3,
+        from: 700,
+        payload: vec![1, 2, 3],
+    };
+    let mut bytes = frame::encode_frame(&raw);
+    bytes.extend(frame::encode_frame(&RawPayload {
+        to: 1,
+        from: 2,
+        payload: Vec::new(),
+    }));
+
+    let mut reader = bytes.as_slice();
+    let first = frame::read_frame(&mut reader).await.unwrap().unwrap();
+    assert_eq!(
+        (first.to, first.from, first.payload),
+        (3, 700, vec![1, 2, 3])
+    );
+    let second = frame::read_frame(&mut reader).await.unwrap().unwrap();
+    assert_eq!((second.to, second.from), (1, 2));
+    assert!(second.payload.is_empty());
+    assert!(frame::read_frame(&mut reader).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn frame_rejects_invalid_lengths() {
+    for len in [0u32, 3, frame::MAX_FRAME_LEN as u32 + 1] {
+        let bytes = len.to_le_bytes();
+        let error = frame::read_frame(&mut bytes.as_slice()).await.unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    // Cut off inside a frame
+    let bytes = frame::encode_frame(&RawPayload {
+        to: 1,
+        from: 2,
+        payload: vec![0; 8],
+    });
+    let error = frame::read_frame(&mut &bytes[..10]).await.unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn proxy_reaches_remote_blox() {
+    let (a, b) = tokio::net::UnixStream::pair().unwrap();
+    let (counter, mut counter_receiver) = counter_channel(20);
+    let gateway_a = spawn_gateway(1, a, []);
+    let _gateway_b = spawn_gateway(2, b, [route::<CounterPayload, Postcard>(counter)]);
+
+    let remote = proxy::<CounterPayload, Postcard>(&gateway_a.gateway_handle, 20);
+    remote
+        .try_send(Message::new(30, CounterPayload::Increment(7)))
+        .unwrap();
+
+    let message = timeout(TIMEOUT, counter_receiver.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(message.source_id, 30);
+    assert!(matches!(message.payload, CounterPayload::Increment(7)));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn raw_messages_and_exposed_routes() {
+    let (a, b) = tokio::net::UnixStream::pair().unwrap();
+    let gateway_a = spawn_gateway(1, a, []);
+    let gateway_b = spawn_gateway(2, b, []);
+
+    let (raw_handle, mut raw_receiver) = TokioMessageHandle::create_channel_with_size(40, 8);
+    gateway_b
+        .gateway_handle
+        .try_send(Message::new(
+            40,
+            GatewayPayload::Expose(40, raw_route(raw_handle).1),
+        ))
+        .unwrap();
+    gateway_a
+        .standard_handle
+        .try_send(Message::new(
+            41,
+            StandardPayload::RawOutbound(40, vec![9, 8]),
+        ))
+        .unwrap();
+
+    let message = timeout(TIMEOUT, raw_receiver.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        message.payload,
+        StandardPayload::RawInbound(41, ref bytes) if bytes == &[9, 8]
+    ));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn shutdown_closes_both_sides() {
+    let (a, b) = tokio::net::UnixStream::pair().unwrap();
+    let (counter, mut counter_receiver) = counter_channel(20);
+    let gateway_a = spawn_gateway(1, a, []);
+    let gateway_b = spawn_gateway(2, b, [route::<CounterPayload, Postcard>(counter)]);
+
+    gateway_a
+        .standard_handle
+        .try_send(Message::new(0, StandardPayload::Shutdown))
+        .unwrap();
+
+    // The remote gateway sees the end of the stream, closes and drops its routes
+    assert!(timeout(TIMEOUT, counter_receiver.recv())
+        .await
+        .unwrap()
+        .is_none());
+    timeout(TIMEOUT, gateway_a.gateway_handle.sender().closed())
+        .await
+        .unwrap();
+    timeout(TIMEOUT, gateway_b.gateway_handle.sender().closed())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn full_writer_drops_frames_and_stays_open() {
+    // Nothing reads the other end, so the writer blocks once the pipe is full
+    let (a, mut b) = tokio::io::duplex(64);
+    let gateway = spawn_gateway(1, a, []);
+    for i in 0..100u8 {
+        let outbound = StandardPayload::RawOutbound(1, vec![i]);
+        gateway
+            .standard_handle
+            .sender()
+            .send(Message::new(41, outbound))
+            .await
+            .unwrap();
+    }
+
+    let mut written = 0;
+    while let Ok(frame) = timeout(Duration::from_millis(200), frame::read_frame(&mut b)).await {
+        assert_eq!(frame.unwrap().unwrap().payload, vec![written]);
+        written += 1;
+    }
+    assert!(written < 100);
+
+    // Still open once the writer has caught up
+    gateway
+        .standard_handle
+        .try_send(Message::new(41, StandardPayload::RawOutbound(2, vec![])))
+        .unwrap();
+    let frame = timeout(TIMEOUT, frame::read_frame(&mut b))
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(frame.to, 2);
+}
+
+#[tokio::test]
+async fn shutdown_stops_reading() {
+    let (a, mut b) = tokio::io::duplex(64);
+    let gateway = spawn_gateway(1, a, []);
+    gateway
+        .standard_handle
+        .try_send(Message::new(0, StandardPayload::Shutdown))
+        .unwrap();
+    assert!(timeout(TIMEOUT, frame::read_frame(&mut b))
+        .await
+        .unwrap()
+        .unwrap()
+        .is_none());
+
+    // The start of a frame the remote side never finishes, a reader still running
+    // would wait for the rest and keep its end of the stream open
+    let header = (frame::MAX_FRAME_LEN as u32).to_le_bytes();
+    let closed = timeout(TIMEOUT, async {
+        if b.write_all(&header).await.is_err() {
+            return;
+        }
+        while b.write_all(&[0]).await.is_ok() {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await;
+    assert!(closed.is_ok());
+}
+
+#[tokio::test]
+async fn error_state_closes_the_gateway() {
+    let (writer, mut written) = mpsc::channel(8);
+    let reader = tokio::spawn(std::future::pending::<()>());
+    let extended_state = GatewayExtendedState::new(GatewayInitArgs {
+        writer,
+        reader: reader.abort_handle(),
+        routes: Vec::new(),
+    });
+    let handles = GatewayHandles {
+        standard_handle: TokioMessageHandle::create_channel_with_size(1, 8).0,
+        gateway_handle: TokioMessageHandle::create_channel_with_size(2, 8).0,
+    };
+
+    let mut harness = StateMachineHarness::<GatewayComponents>::new(extended_state, handles);
+    harness
+        .init(
+            GatewayStateEnum::Uninit(Uninit),
+            GatewayStateEnum::Error(Error),
+        )
+        .expect_finished();
+
+    assert!(harness.state_machine.extended_state.writer.is_none());
+    assert!(timeout(TIMEOUT, written.recv()).await.unwrap().is_none());
+    assert!(timeout(TIMEOUT, reader)
+        .await
+        .unwrap()
+        .unwrap_err()
+        .is_cancelled());
+}
+
+/// Runs this test binary again as the peer, see `peer_process`.  The peer sends
+/// `Increment(7)` from its blox 30 to this process's blox 20, which replies with
+/// `SetCount(7)`.
+#[tokio::test]
+async fn two_processes_over_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let mut child = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "peer_process", "--nocapture"])
+        .env(PEER_ENV, listener.local_addr().unwrap().to_string())
+        .spawn()
+        .unwrap();
+
+    let (counter, mut counter_receiver) = counter_channel(20);
+    let gateway = timeout(
+        TIMEOUT,
+        accept_tcp(1, &listener, [route::<CounterPayload, Postcard>(counter)]),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    let message = timeout(TIMEOUT, counter_receiver.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(message.source_id, 30);
+    assert!(matches!(message.payload, CounterPayload::Increment(7)));
+
+    let peer = proxy::<CounterPayload, Postcard>(&gateway.gateway_handle, message.source_id);
+    peer.try_send(Message::new(20, CounterPayload::SetCount(7)))
+        .unwrap();
+
+    let status = timeout(TIMEOUT, tokio::task::spawn_blocking(move || child.wait()))
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert!(status.success());
+}
+
+/// The peer side of `two_processes_over_tcp`, passes trivially when run on its own
+#[tokio::test]
+async fn peer_process() {
+    let Ok(addr) = std::env::var(PEER_ENV) else {
+        return;
+    };
+
+    let (counter, mut counter_receiver) = counter_channel(30);
+    let gateway = connect_tcp(2, addr, [route::<CounterPayload, Postcard>(counter)])
+        .await
+        .unwrap();
+
+    let remote = proxy::<CounterPayload, Postcard>(&gateway.gateway_handle, 20);
+    remote
+        .try_send(Message::new(30, CounterPayload::Increment(7)))
+        .unwrap();
+
+    let reply = timeout(TIMEOUT, counter_receiver.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reply.source_id, 20);
+    assert!(matches!(reply.payload, CounterPayload::SetCount(7)));
+}
@@ -0,0 +1,158 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+// Measures dispatch throughput and counts heap allocations per message.
+// Run with `cargo bench -p bloxide-core --bench dispatch`
+
+use bloxide_core::{components::*, messaging::*, state_machine::*};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const MESSAGES: usize = 1_000_000;
+
+struct BenchComponents;
+
+impl Components for BenchComponents {
+    type ExtendedState = BenchExtState;
+    type States = BenchStates;
+    type MessageSet = BenchMessage;
+    type Receivers = ();
+    type Handles = ();
+}
+
+struct BenchExtState {
+    handled: usize,
+}
+
+impl ExtendedState for BenchExtState {
+    type InitArgs = ();
+    fn new(_args: Self::InitArgs) -> Self {
+        Self { handled: 0 }
+    }
+}
+
+#[derive(Debug)]
+enum BenchMessage {
+    // Handled by the leaf state without a transition
+    Tick,
+    // Forwarded to the parent, which transitions to the other branch
+    Toggle,
+}
+
+impl MessageSet for BenchMessage {
+    fn source_id(&self) -> u16 {
+        0
+    }
+}
+
+// Uninit
+// ├── Left
+// │   └── LeftLeaf
+// └── Right
+//     └── RightLeaf
+#[derive(Clone, PartialEq, Debug, Default)]
+enum BenchStates {
+    #[default]
+    Uninit,
+    Left,
+    LeftLeaf,
+    Right,
+    RightLeaf,
+}
+
+impl StateEnum for BenchStates {
+    fn all_states() -> &'static [Self] {
+        &[
+            BenchStates::Uninit,
+            BenchStates::Left,
+            BenchStates::LeftLeaf,
+            BenchStates::Right,
+            BenchStates::RightLeaf,
+        ]
+    }
+}
+
+impl State<BenchComponents> for BenchStates {
+    fn parent(&self) -> BenchStates {
+        match self {
+            BenchStates::Uninit | BenchStates::Left | BenchStates::Right => BenchStates::Uninit,
+            BenchStates::LeftLeaf => BenchStates::Left,
+            BenchStates::RightLeaf => BenchStates::Right,
+        }
+    }
+
+    fn handle_message(
+        &self,
+        state_machine: &mut StateMachine<BenchComponents>,
+        message: BenchMessage,
+    ) -> Option<Transition<BenchStates, BenchMessage>> {
+        match (self, message) {
+            (BenchStates::LeftLeaf | BenchStates::RightLeaf, BenchMessage::Tick) => {
+                state_machine.extended_state.handled += 1;
+                None
+            }
+            (BenchStates::LeftLeaf | BenchStates::RightLeaf, message) => {
+                Some(Transition::Parent(message))
+            }
+            (BenchStates::Left, BenchMessage::Toggle) => {
+                Some(Transition::To(BenchStates::RightLeaf))
+            }
+            (BenchStates::Right, BenchMessage::Toggle) => {
+                Some(Transition::To(BenchStates::LeftLeaf))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn run(name: &str, mut message: impl FnMut(usize) -> BenchMessage) {
+    let mut state_machine = StateMachine::<BenchComponents>::new(BenchExtState::new(()), ());
+    state_machine.init(&BenchStates::Uninit, &BenchStates::LeftLeaf);
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for i in 0..MESSAGES {
+        state_machine.dispatch(message(i));
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{:<12} {:>8.1} ns/message {:>6} allocations over {} messages",
+        name,
+        elapsed.as_nanos() as f64 / MESSAGES as f64,
+        allocations,
+        MESSAGES
+    );
+    assert_eq!(allocations, 0, "dispatch allocated on the heap");
+}
+
+fn main() {
+    run("no-transition", |_| BenchMessage::Tick);
+    run("transition", |_| BenchMessage::Toggle);
+    run("mixed", |i| {
+        if i % 4 == 0 {
+            BenchMessage::Toggle
+        } else {
+            BenchMessage::Tick
+        }
+    });
+}
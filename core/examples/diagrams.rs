@@ -0,0 +1,42 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+// Prints the state diagrams of the demo bloxes and the supervisor.
+//
+//     cargo run -p bloxide-core --example diagrams --features testing -- [mermaid|plantuml|dot]
+
+use bloxide_core::{
+    blox::{demo_counter, demo_root, supervisor},
+    diagram::*,
+    testing::RecordingRuntime,
+};
+
+fn main() {
+    let format = match std::env::args().nth(1).as_deref() {
+        None | Some("mermaid") => DiagramFormat::Mermaid,
+        Some("plantuml") => DiagramFormat::PlantUml,
+        Some("dot") => DiagramFormat::Dot,
+        Some(other) => {
+            eprintln!(
+                "Unknown format {}, expected mermaid, plantuml or dot",
+                other
+            );
+            std::process::exit(1);
+        }
+    };
+
+    use demo_counter::{components::CounterComponents, states::*};
+    let counter = StateDiagram::new::<CounterComponents<RecordingRuntime>>("Counter")
+        .with_initial(&CounterStateEnum::NotStarted(NotStarted));
+
+    use demo_root::{components::RootComponents, states::*};
+    let root = StateDiagram::new::<RootComponents<RecordingRuntime>>("Root")
+        .with_initial(&RootStates::Starting(Starting));
+
+    use supervisor::{components::SupervisorComponents, states::*};
+    let supervisor = StateDiagram::new::<SupervisorComponents<RecordingRuntime>>("Supervisor")
+        .with_initial(&SupervisorStateEnum::Running(Running));
+
+    println!("{}", counter.render(format));
+    println!("{}", root.render(format));
+    println!("{}", supervisor.render(format));
+}
@@ -0,0 +1,58 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::{components::DynamicRuntime, messaging::*, std_exports::*};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+use futures_util::future::{abortable, AbortHandle};
+use log::*;
+
+/// A do-activity started by a state.
+///
+/// The activity runs on the runtime's executor and its output is sent to the blox
+/// through one of its own handles, so it arrives as a regular message.  The state
+/// machine aborts the activity when the state that started it exits.
+pub(crate) struct Activity {
+    // Index of the owning state in the state table
+    pub(crate) owner: usize,
+    abort_handle: AbortHandle,
+    // Set once the activity has delivered its result
+    finished: Arc<AtomicBool>,
+}
+
+impl Activity {
+    pub(crate) fn spawn<R, P, F>(owner: usize, handle: &R::MessageHandle<P>, activity: F) -> Self
+    where
+        R: DynamicRuntime,
+        P: Send + 'static,
+        F: Future<Output = P> + Send + 'static,
+    {
+        let handle = handle.clone();
+        let finished = Arc::new(AtomicBool::new(false));
+        let done = finished.clone();
+        let (future, abort_handle) = abortable(async move {
+            let payload = activity.await;
+            if let Err(e) = handle.try_send(Message::new(handle.id(), payload)) {
+                error!("Failed to deliver activity result: {:?}", e);
+            }
+            done.store(true, Ordering::Release);
+        });
+        R::spawn(async move {
+            if future.await.is_err() {
+                trace!("Activity aborted");
+            }
+        });
+        Self {
+            owner,
+            abort_handle,
+            finished,
+        }
+    }
+
+    pub(crate) fn abort(&self) {
+        self.abort_handle.abort();
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright 2025 Bloxide, all rights reserved
 
 use super::{ext_state::*, messaging::*, states::*};
-use crate::{components::*, merge::*, messaging::*, std_exports::*};
+use crate::blox::supervisor::messaging::SupervisorPayload;
+use crate::{components::*, merge::*, messaging::*, spans::in_blox_span, std_exports::*};
 use futures_util::stream::StreamExt;
 use log::*;
 pub struct CounterComponents<R: Runtime>
@@ -9,7 +10,7 @@ where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
 {
-    phantom: std::marker::PhantomData<R>,
+    phantom: PhantomData<R>,
 }
 
 impl<R: Runtime> Components for CounterComponents<R>
@@ -42,6 +43,7 @@ where
     pub counter_receiver: <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType,
 }
 
+#[cfg(feature = "alloc")]
 impl<R: Runtime> Runnable<CounterComponents<R>> for Blox<CounterComponents<R>>
 where
     R::MessageHandle<StandardPayload<R>>:
@@ -51,8 +53,23 @@ where
         MessageSender<PayloadType = CounterPayload> + Clone + Send + 'static,
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send,
 {
-    fn run(mut self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
-        Box::pin(async move {
+    fn run(self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
+        Box::pin((*self).run_static())
+    }
+}
+
+impl<R: Runtime> RunnableStatic<CounterComponents<R>> for Blox<CounterComponents<R>>
+where
+    R::MessageHandle<StandardPayload<R>>:
+        MessageSender<PayloadType = StandardPayload<R>> + Clone + Send + 'static,
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+    R::MessageHandle<CounterPayload>:
+        MessageSender<PayloadType = CounterPayload> + Clone + Send + 'static,
+    <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send,
+{
+    fn run_static(mut self) -> impl Future<Output = ()> + Send + 'static {
+        let id = self.state_machine.self_handles.standard_handle.id();
+        in_blox_span("counter", id, async move {
             self.state_machine.init(
                 &CounterStateEnum::Uninit(Uninit),
                 &CounterStateEnum::NotStarted(NotStarted),
@@ -63,22 +80,35 @@ where
 
             let mut merged = MergedStream2::new(standard_stream, counter_stream);
 
-            while let Some(item) = merged.next().await {
+            while !self.state_machine.is_finished() {
+                let Some(item) = merged.next().await else {
+                    break;
+                };
                 match item {
                     MergedItem::From1(std_msg) => {
                         let msg = CounterMessageSet::StandardMessage(std_msg);
-                        let current_state = self.state_machine.current_state.clone();
-                        self.state_machine.dispatch(msg, &current_state);
+                        self.state_machine.dispatch(msg);
                     }
                     MergedItem::From2(sup_msg) => {
                         let msg = CounterMessageSet::CounterMessage(sup_msg);
-                        let current_state = self.state_machine.current_state.clone();
-                        self.state_machine.dispatch(msg, &current_state);
+                        self.state_machine.dispatch(msg);
                     }
                 }
             }
 
-            trace!("All channels closed. Supervisor run loop complete.");
+            if self.state_machine.is_finished() {
+                trace!("State machine finished. Counter run loop complete.");
+                let supervisor_handle = &self.state_machine.extended_state.supervisor_handle;
+                if let Err(e) = supervisor_handle.try_send(Message::new(
+                    self.state_machine.self_handles.standard_handle.id(),
+                    SupervisorPayload::Finished,
+                )) {
+                    error!("Failed to send message: {:?}", e);
+                }
+                return;
+            }
+
+            trace!("All channels closed. Counter run loop complete.");
         })
     }
 }
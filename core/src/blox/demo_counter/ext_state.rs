@@ -2,14 +2,14 @@
 
 use super::messaging::CounterPayload;
 use crate::components::Runtime;
-use crate::{state_machine::*, SupervisorPayload};
+use crate::{state_machine::*, std_exports::*, SupervisorPayload};
 
 #[derive(Default)]
 pub struct CounterExtendedState<R: Runtime> {
     pub count: usize,
     pub max: usize,
     pub min: usize,
-    pub subscribers: Vec<R::MessageHandle<CounterPayload>>,
+    pub subscribers: HandleVec<R::MessageHandle<CounterPayload>>,
     pub supervisor_handle: R::MessageHandle<SupervisorPayload>,
 }
 
@@ -24,7 +24,7 @@ impl<R: Runtime> ExtendedState for CounterExtendedState<R> {
             count: 0,
             max: 10,
             min: 0,
-            subscribers: Vec::new(),
+            subscribers: HandleVec::new(),
             supervisor_handle: args.supervisor_handle,
         }
     }
@@ -1,7 +1,12 @@
 // Copyright 2025 Bloxide, all rights reserved
 
 use crate::components::Runtime;
+#[cfg(feature = "record")]
+use crate::record::*;
 use crate::{messaging::*, std_exports::*};
+use serde::{Deserialize, Serialize};
+// Without alloc raw payloads are stored inline, boxing them would need a heap
+#[cfg_attr(not(feature = "alloc"), allow(clippy::large_enum_variant))]
 pub enum CounterMessageSet<R: Runtime>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
@@ -15,23 +20,113 @@ where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
 {
+    fn source_id(&self) -> u16 {
+        match self {
+            CounterMessageSet::StandardMessage(msg) => msg.source_id(),
+            CounterMessageSet::CounterMessage(msg) => msg.source_id(),
+        }
+    }
 }
 
-#[derive(Debug)]
+impl<R: Runtime> fmt::Debug for CounterMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
+    <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CounterMessageSet::StandardMessage(msg) => write!(
+                f,
+                "StandardMessage from {}: {:?}",
+                msg.source_id, msg.payload
+            ),
+            CounterMessageSet::CounterMessage(msg) => write!(
+                f,
+                "CounterMessage from {}: {:?}",
+                msg.source_id, msg.payload
+            ),
+        }
+    }
+}
+
+/// Recorded form of `CounterMessageSet`, see `record`
+#[cfg(feature = "record")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CounterRecord {
+    StandardMessage(Message<StandardRecord>),
+    CounterMessage(Message<CounterPayload>),
+}
+
+#[cfg(feature = "record")]
+impl<R: Runtime> RecordableMessageSet for CounterMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
+    <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
+{
+    type Record = CounterRecord;
+
+    fn to_record(&self) -> Option<CounterRecord> {
+        match self {
+            CounterMessageSet::StandardMessage(msg) => {
+                let payload = StandardRecord::from_payload(&msg.payload)?;
+                Some(CounterRecord::StandardMessage(Message::new(
+                    msg.source_id,
+                    payload,
+                )))
+            }
+            CounterMessageSet::CounterMessage(msg) => {
+                Some(CounterRecord::CounterMessage(msg.clone()))
+            }
+        }
+    }
+
+    fn from_record(record: CounterRecord) -> Self {
+        match record {
+            CounterRecord::StandardMessage(msg) => CounterMessageSet::StandardMessage(
+                Message::new(msg.source_id, msg.payload.into_payload()),
+            ),
+            CounterRecord::CounterMessage(msg) => CounterMessageSet::CounterMessage(msg),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CounterPayload {
-    SetCount(Box<usize>),
-    Increment(Box<usize>),
-    Decrement(Box<usize>),
-    SetMax(Box<usize>),
-    SetMin(Box<usize>),
-    CountEvent(Box<CountEvent>),
+    SetCount(usize),
+    Increment(usize),
+    Decrement(usize),
+    SetMax(usize),
+    SetMin(usize),
+    CountEvent(CountEvent),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CountEvent {
     GetCount,
-    MaxReached,
-    MinReached,
     Reset,
     StartCounting,
 }
+
+crate::wire_tags! {
+    CounterPayload = 1,
+}
+
+impl<R: Runtime> From<Message<StandardPayload<R>>> for CounterMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
+    <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
+{
+    fn from(msg: Message<StandardPayload<R>>) -> Self {
+        CounterMessageSet::StandardMessage(msg)
+    }
+}
+
+impl<R: Runtime> From<Message<CounterPayload>> for CounterMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
+    <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
+{
+    fn from(msg: Message<CounterPayload>) -> Self {
+        CounterMessageSet::CounterMessage(msg)
+    }
+}
@@ -16,54 +16,50 @@ where
         CounterStateEnum::Uninit(Uninit)
     }
 
-    fn handle_message(
+    fn try_handle_message(
         &self,
         state_machine: &mut StateMachine<CounterComponents<R>>,
         message: CounterMessageSet<R>,
-    ) -> Option<Transition<CounterStateEnum, CounterMessageSet<R>>> {
-        match message {
+    ) -> HandlerResult<CounterStateEnum, CounterMessageSet<R>> {
+        let transition = match message {
             CounterMessageSet::CounterMessage(msg) => match &msg.payload {
                 CounterPayload::Increment(amount) => {
-                    state_machine.extended_state.count += **amount;
+                    state_machine.extended_state.count = state_machine
+                        .extended_state
+                        .count
+                        .checked_add(*amount)
+                        .ok_or_else(|| StateError::other("Count overflow"))?;
                     if state_machine.extended_state.count >= state_machine.extended_state.max {
-                        for subscriber in state_machine.extended_state.subscribers.iter() {
-                            let _ = subscriber.try_send(Message::new(
-                                state_machine.self_handles.standard_handle.id(),
-                                CounterPayload::CountEvent(Box::new(CountEvent::MaxReached)),
-                            ));
-                        }
                         Some(Transition::To(CounterStateEnum::Finished(Finished)))
                     } else {
                         None
                     }
                 }
                 CounterPayload::Decrement(amount) => {
-                    state_machine.extended_state.count -= **amount;
+                    state_machine.extended_state.count = state_machine
+                        .extended_state
+                        .count
+                        .checked_sub(*amount)
+                        .ok_or_else(|| StateError::other("Count underflow"))?;
                     if state_machine.extended_state.count <= state_machine.extended_state.min {
-                        for subscriber in state_machine.extended_state.subscribers.iter() {
-                            let _ = subscriber.try_send(Message::new(
-                                state_machine.self_handles.standard_handle.id(),
-                                CounterPayload::CountEvent(Box::new(CountEvent::MinReached)),
-                            ));
-                        }
                         Some(Transition::To(CounterStateEnum::Finished(Finished)))
                     } else {
                         None
                     }
                 }
-                CounterPayload::CountEvent(event) => match **event {
+                CounterPayload::CountEvent(event) => match *event {
                     CountEvent::GetCount => {
                         debug!(
                             "[Counting] Current count: {} Max: {}",
                             state_machine.extended_state.count, state_machine.extended_state.max
                         );
                         for subscriber in state_machine.extended_state.subscribers.iter() {
-                            let _ = subscriber.try_send(Message::new(
-                                state_machine.self_handles.standard_handle.id(),
-                                CounterPayload::SetCount(Box::new(
-                                    state_machine.extended_state.count,
-                                )),
-                            ));
+                            subscriber
+                                .try_send(Message::new(
+                                    state_machine.self_handles.standard_handle.id(),
+                                    CounterPayload::SetCount(state_machine.extended_state.count),
+                                ))
+                                .map_err(StateError::send)?;
                         }
                         None
                     }
@@ -75,6 +71,7 @@ where
                 _ => None,
             },
             _ => None,
-        }
+        };
+        Ok(transition)
     }
 }
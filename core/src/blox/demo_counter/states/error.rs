@@ -22,13 +22,10 @@ where
     ) -> Option<Transition<CounterStateEnum, CounterMessageSet<R>>> {
         match message {
             CounterMessageSet::CounterMessage(msg) => match &msg.payload {
-                CounterPayload::CountEvent(event) => match **event {
-                    CountEvent::Reset => {
-                        state_machine.extended_state.count = 0;
-                        Some(Transition::To(CounterStateEnum::NotStarted(NotStarted)))
-                    }
-                    _ => None,
-                },
+                CounterPayload::CountEvent(CountEvent::Reset) => {
+                    state_machine.extended_state.count = 0;
+                    Some(Transition::To(CounterStateEnum::NotStarted(NotStarted)))
+                }
                 _ => None,
             },
             _ => None,
@@ -13,27 +13,20 @@ where
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
 {
     fn parent(&self) -> CounterStateEnum {
-        CounterStateEnum::Idle(Idle)
+        CounterStateEnum::Uninit(Uninit)
+    }
+
+    // Reaching Finished completes the counter, its run loop exits and notifies the supervisor
+    fn is_final(&self) -> bool {
+        true
     }
 
     fn handle_message(
         &self,
-        state_machine: &mut StateMachine<CounterComponents<R>>,
-        message: CounterMessageSet<R>,
+        _state_machine: &mut StateMachine<CounterComponents<R>>,
+        _message: CounterMessageSet<R>,
     ) -> Option<Transition<CounterStateEnum, CounterMessageSet<R>>> {
-        match message {
-            CounterMessageSet::CounterMessage(msg) => match &msg.payload {
-                CounterPayload::CountEvent(event) => match **event {
-                    CountEvent::Reset => {
-                        state_machine.extended_state.count = 0;
-                        Some(Transition::To(CounterStateEnum::NotStarted(NotStarted)))
-                    }
-                    _ => None,
-                },
-                _ => None,
-            },
-            _ => None,
-        }
+        None
     }
 
     fn on_entry(&self, data: &mut StateMachine<CounterComponents<R>>) {
@@ -30,7 +30,43 @@ impl Default for CounterStateEnum {
     }
 }
 
-impl StateEnum for CounterStateEnum {}
+impl StateEnum for CounterStateEnum {
+    fn all_states() -> &'static [Self] {
+        &[
+            CounterStateEnum::Uninit(Uninit),
+            CounterStateEnum::Idle(Idle),
+            CounterStateEnum::NotStarted(NotStarted),
+            CounterStateEnum::Counting(Counting),
+            CounterStateEnum::Finished(Finished),
+            CounterStateEnum::Error(Error),
+        ]
+    }
+
+    fn transitions() -> &'static [(Self, Self)] {
+        &[
+            (
+                CounterStateEnum::NotStarted(NotStarted),
+                CounterStateEnum::Counting(Counting),
+            ),
+            (
+                CounterStateEnum::Counting(Counting),
+                CounterStateEnum::Finished(Finished),
+            ),
+            (
+                CounterStateEnum::Counting(Counting),
+                CounterStateEnum::NotStarted(NotStarted),
+            ),
+            (
+                CounterStateEnum::Counting(Counting),
+                CounterStateEnum::Error(Error),
+            ),
+        ]
+    }
+
+    fn error_state() -> Option<Self> {
+        Some(CounterStateEnum::Error(Error))
+    }
+}
 
 impl<R: Runtime> State<CounterComponents<R>> for CounterStateEnum
 where
@@ -74,6 +110,77 @@ where
         }
     }
 
+    fn try_on_entry(
+        &self,
+        state_machine: &mut StateMachine<CounterComponents<R>>,
+    ) -> Result<(), StateError> {
+        match self {
+            CounterStateEnum::Uninit(s) => s.try_on_entry(state_machine),
+            CounterStateEnum::NotStarted(s) => s.try_on_entry(state_machine),
+            CounterStateEnum::Idle(s) => s.try_on_entry(state_machine),
+            CounterStateEnum::Counting(s) => s.try_on_entry(state_machine),
+            CounterStateEnum::Finished(s) => s.try_on_entry(state_machine),
+            CounterStateEnum::Error(s) => s.try_on_entry(state_machine),
+        }
+    }
+
+    fn try_on_exit(
+        &self,
+        state_machine: &mut StateMachine<CounterComponents<R>>,
+    ) -> Result<(), StateError> {
+        match self {
+            CounterStateEnum::Uninit(s) => s.try_on_exit(state_machine),
+            CounterStateEnum::NotStarted(s) => s.try_on_exit(state_machine),
+            CounterStateEnum::Idle(s) => s.try_on_exit(state_machine),
+            CounterStateEnum::Counting(s) => s.try_on_exit(state_machine),
+            CounterStateEnum::Finished(s) => s.try_on_exit(state_machine),
+            CounterStateEnum::Error(s) => s.try_on_exit(state_machine),
+        }
+    }
+
+    fn try_handle_message(
+        &self,
+        state_machine: &mut StateMachine<CounterComponents<R>>,
+        message: CounterMessageSet<R>,
+    ) -> HandlerResult<CounterStateEnum, CounterMessageSet<R>> {
+        match self {
+            CounterStateEnum::Uninit(s) => s.try_handle_message(state_machine, message),
+            CounterStateEnum::NotStarted(s) => s.try_handle_message(state_machine, message),
+            CounterStateEnum::Idle(s) => s.try_handle_message(state_machine, message),
+            CounterStateEnum::Counting(s) => s.try_handle_message(state_machine, message),
+            CounterStateEnum::Finished(s) => s.try_handle_message(state_machine, message),
+            CounterStateEnum::Error(s) => s.try_handle_message(state_machine, message),
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        match self {
+            CounterStateEnum::Uninit(s) => <Uninit as State<CounterComponents<R>>>::is_final(s),
+            CounterStateEnum::NotStarted(s) => {
+                <NotStarted as State<CounterComponents<R>>>::is_final(s)
+            }
+            CounterStateEnum::Idle(s) => <Idle as State<CounterComponents<R>>>::is_final(s),
+            CounterStateEnum::Counting(s) => <Counting as State<CounterComponents<R>>>::is_final(s),
+            CounterStateEnum::Finished(s) => <Finished as State<CounterComponents<R>>>::is_final(s),
+            CounterStateEnum::Error(s) => <Error as State<CounterComponents<R>>>::is_final(s),
+        }
+    }
+
+    fn on_completion(
+        &self,
+        state_machine: &mut StateMachine<CounterComponents<R>>,
+        final_state: &CounterStateEnum,
+    ) -> Option<CounterStateEnum> {
+        match self {
+            CounterStateEnum::Uninit(s) => s.on_completion(state_machine, final_state),
+            CounterStateEnum::NotStarted(s) => s.on_completion(state_machine, final_state),
+            CounterStateEnum::Idle(s) => s.on_completion(state_machine, final_state),
+            CounterStateEnum::Counting(s) => s.on_completion(state_machine, final_state),
+            CounterStateEnum::Finished(s) => s.on_completion(state_machine, final_state),
+            CounterStateEnum::Error(s) => s.on_completion(state_machine, final_state),
+        }
+    }
+
     fn parent(&self) -> CounterStateEnum {
         match self {
             CounterStateEnum::Uninit(s) => <Uninit as State<CounterComponents<R>>>::parent(s),
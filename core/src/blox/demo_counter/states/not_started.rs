@@ -17,15 +17,15 @@ where
         CounterStateEnum::Idle(Idle)
     }
 
-    fn handle_message(
+    fn try_handle_message(
         &self,
         state_machine: &mut StateMachine<CounterComponents<R>>,
         message: CounterMessageSet<R>,
-    ) -> Option<Transition<CounterStateEnum, CounterMessageSet<R>>> {
-        match message {
+    ) -> HandlerResult<CounterStateEnum, CounterMessageSet<R>> {
+        let transition = match message {
             CounterMessageSet::CounterMessage(msg) => match &msg.payload {
                 CounterPayload::SetCount(new_value) => {
-                    state_machine.extended_state.count = **new_value;
+                    state_machine.extended_state.count = *new_value;
                     debug!(
                         "State: {:?} Set count to {}",
                         self, state_machine.extended_state.count
@@ -33,7 +33,7 @@ where
                     None
                 }
                 CounterPayload::SetMax(new_max) => {
-                    state_machine.extended_state.max = **new_max;
+                    state_machine.extended_state.max = *new_max;
                     debug!(
                         "State: {:?} New max set to {}",
                         self, state_machine.extended_state.max
@@ -41,7 +41,7 @@ where
                     None
                 }
                 CounterPayload::SetMin(new_min) => {
-                    state_machine.extended_state.min = **new_min;
+                    state_machine.extended_state.min = *new_min;
                     debug!(
                         "State: {:?} New min set to {}",
                         self, state_machine.extended_state.min
@@ -50,7 +50,7 @@ where
                 }
                 CounterPayload::CountEvent(event) => {
                     trace!("State: {:?} Received CountEvent: {:?}", self, event);
-                    match **event {
+                    match *event {
                         CountEvent::GetCount => {
                             debug!(
                                 "State: {:?} Current count: {}",
@@ -60,16 +60,16 @@ where
                             None
                         }
                         CountEvent::StartCounting => {
-                            state_machine.extended_state.subscribers.iter().for_each(
-                                |subscriber| {
-                                    let _ = subscriber.try_send(Message::new(
+                            for subscriber in state_machine.extended_state.subscribers.iter() {
+                                subscriber
+                                    .try_send(Message::new(
                                         state_machine.self_handles.standard_handle.id(),
-                                        CounterPayload::SetCount(Box::new(
+                                        CounterPayload::SetCount(
                                             state_machine.extended_state.count,
-                                        )),
-                                    ));
-                                },
-                            );
+                                        ),
+                                    ))
+                                    .map_err(StateError::send)?;
+                            }
                             Some(Transition::To(CounterStateEnum::Counting(Counting)))
                         }
                         _ => None,
@@ -78,6 +78,7 @@ where
                 _ => None,
             },
             _ => None,
-        }
+        };
+        Ok(transition)
     }
 }
@@ -2,20 +2,19 @@
 
 use super::{ext_state::*, messaging::*, states::*};
 use crate::blox::demo_counter::messaging::CounterPayload;
-use crate::{components::*, merge::*, messaging::*};
+use crate::blox::supervisor::messaging::SupervisorPayload;
+use crate::{components::*, merge::*, messaging::*, spans::in_blox_span, std_exports::*};
 use futures_util::StreamExt;
 use log::*;
-use std::future::Future;
-use std::pin::Pin;
-pub struct RootComponents<R: Runtime>
+pub struct RootComponents<R: DynamicRuntime>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
 {
-    phantom: std::marker::PhantomData<R>,
+    phantom: PhantomData<R>,
 }
 
-impl<R: Runtime> Components for RootComponents<R>
+impl<R: DynamicRuntime> Components for RootComponents<R>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
@@ -27,7 +26,7 @@ where
     type Handles = RootHandles<R>;
 }
 
-pub struct RootHandles<R: Runtime>
+pub struct RootHandles<R: DynamicRuntime>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
@@ -36,7 +35,7 @@ where
     pub counter_handle: R::MessageHandle<CounterPayload>,
 }
 
-pub struct RootReceivers<R: Runtime>
+pub struct RootReceivers<R: DynamicRuntime>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send + 'static,
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send + 'static,
@@ -45,7 +44,7 @@ where
     pub counter_rx: <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType,
 }
 
-impl<R: Runtime> Runnable<RootComponents<R>> for Blox<RootComponents<R>>
+impl<R: DynamicRuntime> Runnable<RootComponents<R>> for Blox<RootComponents<R>>
 where
     R::MessageHandle<StandardPayload<R>>:
         MessageSender<PayloadType = StandardPayload<R>> + Clone + Send + 'static,
@@ -55,7 +54,8 @@ where
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send,
 {
     fn run(mut self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
-        Box::pin(async move {
+        let id = self.state_machine.self_handles.standard_handle.id();
+        Box::pin(in_blox_span("root", id, async move {
             self.state_machine
                 .init(&RootStates::Uninit(Uninit), &RootStates::Starting(Starting));
 
@@ -64,22 +64,35 @@ where
 
             let mut merged = MergedStream2::new(standard_stream, counter_stream);
 
-            while let Some(item) = merged.next().await {
+            while !self.state_machine.is_finished() {
+                let Some(item) = merged.next().await else {
+                    break;
+                };
                 match item {
                     MergedItem::From1(std_msg) => {
                         let msg = RootMessageSet::StandardMessage(std_msg);
-                        let current_state = self.state_machine.current_state.clone();
-                        self.state_machine.dispatch(msg, &current_state);
+                        self.state_machine.dispatch(msg);
                     }
                     MergedItem::From2(sup_msg) => {
                         let msg = RootMessageSet::CounterMessage(sup_msg);
-                        let current_state = self.state_machine.current_state.clone();
-                        self.state_machine.dispatch(msg, &current_state);
+                        self.state_machine.dispatch(msg);
                     }
                 }
             }
 
-            trace!("All channels closed. Supervisor run loop complete.");
-        })
+            if self.state_machine.is_finished() {
+                trace!("State machine finished. Root run loop complete.");
+                let supervisor_handle = &self.state_machine.extended_state.supervisor_handle;
+                if let Err(e) = supervisor_handle.try_send(Message::new(
+                    self.state_machine.self_handles.standard_handle.id(),
+                    SupervisorPayload::Finished,
+                )) {
+                    error!("Failed to send message: {:?}", e);
+                }
+                return;
+            }
+
+            trace!("All channels closed. Root run loop complete.");
+        }))
     }
 }
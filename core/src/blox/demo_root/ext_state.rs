@@ -1,20 +1,20 @@
 // Copyright 2025 Bloxide, all rights reserved
 
 use crate::blox::demo_counter::messaging::CounterPayload;
-use crate::components::Runtime;
+use crate::components::DynamicRuntime;
 use crate::{state_machine::*, SupervisorPayload};
 #[derive(Debug)]
-pub struct RootExtState<R: Runtime> {
+pub struct RootExtState<R: DynamicRuntime> {
     pub supervisor_handle: R::MessageHandle<SupervisorPayload>,
     pub counter_handle: Option<R::MessageHandle<CounterPayload>>,
 }
 
-pub struct RootInitArgs<R: Runtime> {
+pub struct RootInitArgs<R: DynamicRuntime> {
     pub supervisor_handle: R::MessageHandle<SupervisorPayload>,
     pub counter_handle: Option<R::MessageHandle<CounterPayload>>,
 }
 
-impl<R: Runtime> ExtendedState for RootExtState<R> {
+impl<R: DynamicRuntime> ExtendedState for RootExtState<R> {
     type InitArgs = RootInitArgs<R>;
     fn new(args: Self::InitArgs) -> Self {
         Self {
@@ -1,11 +1,11 @@
 // Copyright 2025 Bloxide, all rights reserved
 
 use crate::blox::demo_counter::messaging::CounterPayload;
-use crate::components::Runtime;
+use crate::components::DynamicRuntime;
 use crate::messaging::MessageSender;
-use crate::{Message, MessageSet, StandardPayload};
+use crate::{fmt, Message, MessageSet, StandardPayload};
 
-pub enum RootMessageSet<R: Runtime>
+pub enum RootMessageSet<R: DynamicRuntime>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
 {
@@ -13,7 +13,52 @@ where
     CounterMessage(Message<CounterPayload>),
 }
 
-impl<R: Runtime> MessageSet for RootMessageSet<R> where
-    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send
+impl<R: DynamicRuntime> MessageSet for RootMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+{
+    fn source_id(&self) -> u16 {
+        match self {
+            RootMessageSet::StandardMessage(msg) => msg.source_id(),
+            RootMessageSet::CounterMessage(msg) => msg.source_id(),
+        }
+    }
+}
+
+impl<R: DynamicRuntime> fmt::Debug for RootMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RootMessageSet::StandardMessage(msg) => write!(
+                f,
+                "StandardMessage from {}: {:?}",
+                msg.source_id, msg.payload
+            ),
+            RootMessageSet::CounterMessage(msg) => write!(
+                f,
+                "CounterMessage from {}: {:?}",
+                msg.source_id, msg.payload
+            ),
+        }
+    }
+}
+
+impl<R: DynamicRuntime> From<Message<StandardPayload<R>>> for RootMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+{
+    fn from(msg: Message<StandardPayload<R>>) -> Self {
+        RootMessageSet::StandardMessage(msg)
+    }
+}
+
+impl<R: DynamicRuntime> From<Message<CounterPayload>> for RootMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
 {
+    fn from(msg: Message<CounterPayload>) -> Self {
+        RootMessageSet::CounterMessage(msg)
+    }
 }
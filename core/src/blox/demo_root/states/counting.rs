@@ -3,7 +3,7 @@
 use super::{RootComponents, RootStates};
 use crate::blox::demo_counter::messaging::*;
 use crate::blox::demo_root::{messaging::*, states::*};
-use crate::components::Runtime;
+use crate::components::DynamicRuntime;
 use crate::{components::*, messaging::*, state_machine::*};
 
 use log::*;
@@ -11,7 +11,7 @@ use log::*;
 #[derive(Clone, PartialEq, Debug)]
 pub struct Counting;
 
-impl<R: Runtime> State<RootComponents<R>> for Counting
+impl<R: DynamicRuntime> State<RootComponents<R>> for Counting
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
@@ -22,43 +22,44 @@ where
         RootStates::Uninit(Uninit)
     }
 
-    fn handle_message(
+    fn try_handle_message(
         &self,
         state_machine: &mut StateMachine<RootComponents<R>>,
         msg: <RootComponents<R> as Components>::MessageSet,
-    ) -> Option<Transition<RootStates, <RootComponents<R> as Components>::MessageSet>> {
-        match msg {
+    ) -> HandlerResult<RootStates, <RootComponents<R> as Components>::MessageSet> {
+        let transition = match msg {
+            RootMessageSet::StandardMessage(msg) => match msg.payload {
+                StandardPayload::ChildFinished(id) => {
+                    debug!("Counter blox {} finished", id);
+                    Some(Transition::To(RootStates::Finished(Finished)))
+                }
+                _ => None,
+            },
             RootMessageSet::CounterMessage(msg) => match msg.payload {
-                CounterPayload::CountEvent(event) => match *event {
-                    CountEvent::MaxReached => Some(Transition::To(RootStates::Finished(Finished))),
-                    CountEvent::MinReached => Some(Transition::To(RootStates::Finished(Finished))),
-                    _ => None,
-                },
                 CounterPayload::SetCount(count) => {
                     info!("Current count: {}", count);
-                    let _ = state_machine
+                    let counter_handle = state_machine
                         .extended_state
                         .counter_handle
                         .as_ref()
-                        .unwrap()
+                        .ok_or(StateError::MissingHandle("counter_handle"))?;
+                    counter_handle
                         .try_send(Message::new(
                             state_machine.self_handles.standard_handle.id(),
-                            CounterPayload::Increment(Box::new(1)),
-                        ));
-                    let _ = state_machine
-                        .extended_state
-                        .counter_handle
-                        .as_ref()
-                        .unwrap()
+                            CounterPayload::Increment(1),
+                        ))
+                        .map_err(StateError::send)?;
+                    counter_handle
                         .try_send(Message::new(
                             state_machine.self_handles.standard_handle.id(),
-                            CounterPayload::CountEvent(Box::new(CountEvent::GetCount)),
-                        ));
+                            CounterPayload::CountEvent(CountEvent::GetCount),
+                        ))
+                        .map_err(StateError::send)?;
                     None
                 }
                 _ => None,
             },
-            _ => None,
-        }
+        };
+        Ok(transition)
     }
 }
@@ -3,13 +3,13 @@
 use super::idle::Idle;
 use super::{RootComponents, RootStates};
 use crate::blox::demo_counter::messaging::CounterPayload;
-use crate::components::Runtime;
+use crate::components::DynamicRuntime;
 use crate::{components::*, messaging::*, state_machine::*};
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Error;
 
-impl<R: Runtime> State<RootComponents<R>> for Error
+impl<R: DynamicRuntime> State<RootComponents<R>> for Error
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
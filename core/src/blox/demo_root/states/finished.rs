@@ -1,16 +1,16 @@
 // Copyright 2025 Bloxide, all rights reserved
 
-use super::idle::Idle;
+use super::uninit::Uninit;
 use super::{RootComponents, RootStates};
 use crate::blox::demo_counter::messaging::CounterPayload;
-use crate::components::Runtime;
+use crate::components::DynamicRuntime;
 use crate::{components::*, messaging::*, state_machine::*};
 use log::*;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Finished;
 
-impl<R: Runtime> State<RootComponents<R>> for Finished
+impl<R: DynamicRuntime> State<RootComponents<R>> for Finished
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
@@ -18,7 +18,11 @@ where
     <R::MessageHandle<CounterPayload> as MessageSender>::ReceiverType: Send,
 {
     fn parent(&self) -> RootStates {
-        RootStates::Idle(Idle)
+        RootStates::Uninit(Uninit)
+    }
+
+    fn is_final(&self) -> bool {
+        true
     }
 
     fn handle_message(
@@ -9,7 +9,7 @@ pub mod uninit;
 
 use super::components::RootComponents;
 use crate::blox::demo_counter::messaging::CounterPayload;
-use crate::components::Runtime;
+use crate::components::DynamicRuntime;
 use crate::{components::*, messaging::*, state_machine::*};
 use counting::Counting;
 use error::Error;
@@ -28,7 +28,7 @@ pub enum RootStates {
     Error(Error),
 }
 
-impl<R: Runtime> State<RootComponents<R>> for RootStates
+impl<R: DynamicRuntime> State<RootComponents<R>> for RootStates
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
@@ -72,6 +72,75 @@ where
         }
     }
 
+    fn try_on_entry(
+        &self,
+        state_machine: &mut StateMachine<RootComponents<R>>,
+    ) -> Result<(), StateError> {
+        match self {
+            RootStates::Uninit(s) => s.try_on_entry(state_machine),
+            RootStates::Counting(s) => s.try_on_entry(state_machine),
+            RootStates::Finished(s) => s.try_on_entry(state_machine),
+            RootStates::Error(s) => s.try_on_entry(state_machine),
+            RootStates::Idle(s) => s.try_on_entry(state_machine),
+            RootStates::Starting(s) => s.try_on_entry(state_machine),
+        }
+    }
+
+    fn try_on_exit(
+        &self,
+        state_machine: &mut StateMachine<RootComponents<R>>,
+    ) -> Result<(), StateError> {
+        match self {
+            RootStates::Uninit(s) => s.try_on_exit(state_machine),
+            RootStates::Counting(s) => s.try_on_exit(state_machine),
+            RootStates::Finished(s) => s.try_on_exit(state_machine),
+            RootStates::Error(s) => s.try_on_exit(state_machine),
+            RootStates::Idle(s) => s.try_on_exit(state_machine),
+            RootStates::Starting(s) => s.try_on_exit(state_machine),
+        }
+    }
+
+    fn try_handle_message(
+        &self,
+        state_machine: &mut StateMachine<RootComponents<R>>,
+        message: <RootComponents<R> as Components>::MessageSet,
+    ) -> HandlerResult<RootStates, <RootComponents<R> as Components>::MessageSet> {
+        match self {
+            RootStates::Uninit(s) => s.try_handle_message(state_machine, message),
+            RootStates::Counting(s) => s.try_handle_message(state_machine, message),
+            RootStates::Finished(s) => s.try_handle_message(state_machine, message),
+            RootStates::Error(s) => s.try_handle_message(state_machine, message),
+            RootStates::Idle(s) => s.try_handle_message(state_machine, message),
+            RootStates::Starting(s) => s.try_handle_message(state_machine, message),
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        match self {
+            RootStates::Uninit(s) => <Uninit as State<RootComponents<R>>>::is_final(s),
+            RootStates::Counting(s) => <Counting as State<RootComponents<R>>>::is_final(s),
+            RootStates::Finished(s) => <Finished as State<RootComponents<R>>>::is_final(s),
+            RootStates::Error(s) => <Error as State<RootComponents<R>>>::is_final(s),
+            RootStates::Idle(s) => <Idle as State<RootComponents<R>>>::is_final(s),
+            RootStates::Starting(s) => <Starting as State<RootComponents<R>>>::is_final(s),
+        }
+    }
+
+    fn on_completion(
+        &self,
+        state_machine: &mut StateMachine<RootComponents<R>>,
+        final_state: &RootStates,
+    ) -> Option<RootStates> {
+        match self {
+            RootStates::Uninit(s) => s.on_completion(state_machine, final_state),
+            RootStates::Counting(s) => s.on_completion(state_machine, final_state),
+            RootStates::Finished(s) => s.on_completion(state_machine, final_state),
+            RootStates::Error(s) => s.on_completion(state_machine, final_state),
+            RootStates::Idle(s) => s.on_completion(state_machine, final_state),
+            RootStates::Starting(s) => s.on_completion(state_machine, final_state),
+        }
+    }
+
     fn parent(&self) -> RootStates {
         match self {
             RootStates::Uninit(s) => <Uninit as State<RootComponents<R>>>::parent(s),
@@ -84,7 +153,35 @@ where
     }
 }
 
-impl StateEnum for RootStates {}
+impl StateEnum for RootStates {
+    fn all_states() -> &'static [Self] {
+        &[
+            RootStates::Uninit(Uninit),
+            RootStates::Idle(Idle),
+            RootStates::Starting(Starting),
+            RootStates::Counting(Counting),
+            RootStates::Finished(Finished),
+            RootStates::Error(Error),
+        ]
+    }
+
+    fn transitions() -> &'static [(Self, Self)] {
+        &[
+            (
+                RootStates::Starting(Starting),
+                RootStates::Counting(Counting),
+            ),
+            (
+                RootStates::Counting(Counting),
+                RootStates::Finished(Finished),
+            ),
+        ]
+    }
+
+    fn error_state() -> Option<Self> {
+        Some(RootStates::Error(Error))
+    }
+}
 
 impl Default for RootStates {
     fn default() -> Self {
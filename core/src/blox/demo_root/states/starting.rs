@@ -4,14 +4,14 @@ use super::{RootComponents, RootStates};
 use crate::blox::demo_counter::{components::*, ext_state::*, messaging::*};
 use crate::blox::demo_root::{messaging::*, states::*};
 use crate::blox::supervisor::messaging::*;
-use crate::components::Runtime;
-use crate::{components::*, messaging::*, state_machine::*};
+use crate::components::DynamicRuntime;
+use crate::{components::*, messaging::*, state_machine::*, std_exports::*};
 use log::*;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Starting;
 
-impl<R: Runtime> State<RootComponents<R>> for Starting
+impl<R: DynamicRuntime> State<RootComponents<R>> for Starting
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
@@ -22,12 +22,12 @@ where
         RootStates::Idle(Idle)
     }
 
-    fn handle_message(
+    fn try_handle_message(
         &self,
         state_machine: &mut StateMachine<RootComponents<R>>,
         msg: <RootComponents<R> as Components>::MessageSet,
-    ) -> Option<Transition<RootStates, <RootComponents<R> as Components>::MessageSet>> {
-        match msg {
+    ) -> HandlerResult<RootStates, <RootComponents<R> as Components>::MessageSet> {
+        let transition = match msg {
             RootMessageSet::StandardMessage(msg) => match msg.payload {
                 StandardPayload::StandardChannel(new_standard_handle, standard_receiver) => {
                     let (counter_handle, counter_receiver) =
@@ -69,56 +69,59 @@ where
                     let spawn_request = SupervisorPayload::Spawn(counter_future);
 
                     let supervisor_handle = state_machine.extended_state.supervisor_handle.clone();
-                    if let Err(e) = supervisor_handle.try_send(Message::new(
-                        state_machine.self_handles.standard_handle.id(),
-                        spawn_request,
-                    )) {
-                        error!("Failed to send message: {:?}", e);
-                    }
-
-                    state_machine
-                        .extended_state
-                        .counter_handle
-                        .as_ref()
-                        .unwrap()
+                    supervisor_handle
                         .try_send(Message::new(
                             state_machine.self_handles.standard_handle.id(),
-                            CounterPayload::SetMax(Box::new(4)),
+                            spawn_request,
                         ))
-                        .unwrap_or_else(|e| error!("Failed to send message: {:?}", e));
+                        .map_err(StateError::send)?;
+
+                    counter_handle
+                        .try_send(Message::new(
+                            state_machine.self_handles.standard_handle.id(),
+                            CounterPayload::SetMax(4),
+                        ))
+                        .map_err(StateError::send)?;
 
                     Some(Transition::To(RootStates::Counting(Counting)))
                 }
                 _ => None,
             },
             _ => None,
-        }
+        };
+        Ok(transition)
     }
 
-    fn on_entry(&self, state_machine: &mut StateMachine<RootComponents<R>>) {
+    fn try_on_entry(
+        &self,
+        state_machine: &mut StateMachine<RootComponents<R>>,
+    ) -> Result<(), StateError> {
         trace!("State on_entry: {:?}", self);
         // Request a new standard handle to start the counter blox
         let handle = state_machine.extended_state.supervisor_handle.clone();
-        if let Err(e) = handle.try_send(Message::new(
-            state_machine.self_handles.standard_handle.id(),
-            SupervisorPayload::RequestNewStandardHandle(32), // TODO: make this configurable
-        )) {
-            error!("Failed to send message: {:?}", e);
-        }
+        handle
+            .try_send(Message::new(
+                state_machine.self_handles.standard_handle.id(),
+                SupervisorPayload::RequestNewStandardHandle(32), // TODO: make this configurable
+            ))
+            .map_err(StateError::send)
     }
 
-    fn on_exit(&self, state_machine: &mut StateMachine<RootComponents<R>>) {
+    fn try_on_exit(
+        &self,
+        state_machine: &mut StateMachine<RootComponents<R>>,
+    ) -> Result<(), StateError> {
         trace!("State on_exit: {:?}", self);
         // Start the counter ping pong
         state_machine
             .extended_state
             .counter_handle
             .as_ref()
-            .unwrap()
+            .ok_or(StateError::MissingHandle("counter_handle"))?
             .try_send(Message::new(
                 state_machine.self_handles.standard_handle.id(),
-                CounterPayload::CountEvent(Box::new(CountEvent::StartCounting)),
+                CounterPayload::CountEvent(CountEvent::StartCounting),
             ))
-            .unwrap_or_else(|e| error!("Failed to send message: {:?}", e));
+            .map_err(StateError::send)
     }
 }
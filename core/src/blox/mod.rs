@@ -1,5 +1,6 @@
 // Copyright 2025 Bloxide, all rights reserved
 
 pub mod demo_counter;
+#[cfg(feature = "alloc")]
 pub mod demo_root;
 pub mod supervisor;
@@ -2,14 +2,14 @@
 
 use super::{ext_state::*, messaging::*, states::*};
 use crate::merge::*;
-use crate::{components::*, messaging::*, std_exports::*};
+use crate::{components::*, messaging::*, spans::in_blox_span, std_exports::*};
 use futures_util::StreamExt;
 use log::*;
-pub struct SupervisorComponents<R: Runtime> {
-    phantom: std::marker::PhantomData<R>,
+pub struct SupervisorComponents<R: DynamicRuntime> {
+    phantom: PhantomData<R>,
 }
 
-pub struct SupervisorHandles<R: Runtime>
+pub struct SupervisorHandles<R: DynamicRuntime>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
     <R::MessageHandle<SupervisorPayload> as MessageSender>::ReceiverType: Send,
@@ -18,7 +18,7 @@ where
     pub supervisor_handle: R::MessageHandle<SupervisorPayload>,
 }
 
-pub struct SupervisorReceivers<R: Runtime>
+pub struct SupervisorReceivers<R: DynamicRuntime>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
     <R::MessageHandle<SupervisorPayload> as MessageSender>::ReceiverType: Send,
@@ -27,7 +27,7 @@ where
     pub supervisor_receiver: <R::MessageHandle<SupervisorPayload> as MessageSender>::ReceiverType,
 }
 
-impl<R: Runtime> Components for SupervisorComponents<R>
+impl<R: DynamicRuntime> Components for SupervisorComponents<R>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
     <R::MessageHandle<SupervisorPayload> as MessageSender>::ReceiverType: Send,
@@ -39,7 +39,7 @@ where
     type Handles = SupervisorHandles<R>;
 }
 
-pub struct SupervisorInitArgs<R: Runtime>
+pub struct SupervisorInitArgs<R: DynamicRuntime>
 where
     R::MessageHandle<StandardPayload<R>>:
         MessageSender<PayloadType = StandardPayload<R>> + Clone + Send + 'static,
@@ -49,7 +49,7 @@ where
     pub root_future: Pin<Box<dyn Future<Output = ()> + Send>>,
 }
 
-impl<R: Runtime> Runnable<SupervisorComponents<R>> for Blox<SupervisorComponents<R>>
+impl<R: DynamicRuntime> Runnable<SupervisorComponents<R>> for Blox<SupervisorComponents<R>>
 where
     R::MessageHandle<StandardPayload<R>>:
         MessageSender<PayloadType = StandardPayload<R>> + Clone + Send + 'static,
@@ -59,7 +59,8 @@ where
     <R::MessageHandle<SupervisorPayload> as MessageSender>::ReceiverType: Send,
 {
     fn run(mut self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
-        Box::pin(async move {
+        let id = self.state_machine.self_handles.standard_handle.id();
+        Box::pin(in_blox_span("supervisor", id, async move {
             self.state_machine.init(
                 &SupervisorStateEnum::Uninit(Uninit),
                 &SupervisorStateEnum::Running(Running),
@@ -74,18 +75,16 @@ where
                 match item {
                     MergedItem::From1(std_msg) => {
                         let msg = SupervisorMessageSet::StandardMessage(std_msg);
-                        let current_state = self.state_machine.current_state.clone();
-                        self.state_machine.dispatch(msg, &current_state);
+                        self.state_machine.dispatch(msg);
                     }
                     MergedItem::From2(sup_msg) => {
                         let msg = SupervisorMessageSet::SupervisorMessage(sup_msg);
-                        let current_state = self.state_machine.current_state.clone();
-                        self.state_machine.dispatch(msg, &current_state);
+                        self.state_machine.dispatch(msg);
                     }
                 }
             }
 
             trace!("All channels closed. Supervisor run loop complete.");
-        })
+        }))
     }
 }
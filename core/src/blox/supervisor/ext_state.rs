@@ -1,23 +1,25 @@
 // Copyright 2025 Bloxide, all rights reserved
 
 use super::components::*;
-use crate::components::Runtime;
+use crate::components::*;
 use crate::{messaging::*, state_machine::*, std_exports::*};
 
 #[derive(Default)]
-pub struct SupervisorExtendedState<R: Runtime>
+pub struct SupervisorExtendedState<R: DynamicRuntime>
 where
     R::MessageHandle<StandardPayload<R>>:
         MessageSender<PayloadType = StandardPayload<R>> + Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
 {
     pub blox: HashMap<u16, R::MessageHandle<StandardPayload<R>>>,
+    // Maps each blox id to the id of the blox that requested its handle
+    pub parents: HashMap<u16, u16>,
     pub next_id: u16,
     pub root_future: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
 }
 
 #[allow(clippy::type_complexity)]
-impl<R: Runtime> SupervisorExtendedState<R>
+impl<R: DynamicRuntime> SupervisorExtendedState<R>
 where
     R::MessageHandle<StandardPayload<R>>:
         MessageSender<PayloadType = StandardPayload<R>> + Clone + Send + 'static,
@@ -39,14 +41,25 @@ where
         (handle, rx)
     }
 
+    /// Removes a finished blox, returning the handle of the blox that requested it, if any
+    pub fn remove_blox(&mut self, id: u16) -> Option<R::MessageHandle<StandardPayload<R>>> {
+        self.blox.remove(&id);
+        let parent = self.parents.remove(&id)?;
+        self.blox.get(&parent).cloned()
+    }
+
     pub fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send>>) -> Result<(), String> {
         R::spawn(future);
         // Add implementation here
         Ok(())
     }
+
+    pub fn spawn_with(&self, mode: SpawnMode, factory: BloxFactory) -> Result<(), SpawnError> {
+        R::spawn_with(mode, factory)
+    }
 }
 
-impl<R: Runtime> ExtendedState for SupervisorExtendedState<R>
+impl<R: DynamicRuntime> ExtendedState for SupervisorExtendedState<R>
 where
     R::MessageHandle<StandardPayload<R>>:
         MessageSender<PayloadType = StandardPayload<R>> + Clone + Send + 'static,
@@ -62,13 +75,14 @@ where
         );
         SupervisorExtendedState {
             blox,
+            parents: HashMap::new(),
             root_future: Some(args.root_future),
             next_id: 2,
         }
     }
 }
 
-impl<R: Runtime> fmt::Debug for SupervisorExtendedState<R>
+impl<R: DynamicRuntime> fmt::Debug for SupervisorExtendedState<R>
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
@@ -1,46 +1,86 @@
 // Copyright 2025 Bloxide, all rights reserved
 
-use crate::components::Runtime;
+use crate::components::*;
 use crate::messaging::*;
 use crate::std_exports::*;
 
-pub enum SupervisorMessageSet<R: Runtime>
+pub enum SupervisorMessageSet<R: DynamicRuntime>
 where
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
 {
     StandardMessage(Message<StandardPayload<R>>),
     SupervisorMessage(Message<SupervisorPayload>),
 }
-impl<R: Runtime> MessageSet for SupervisorMessageSet<R> where
-    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send
+impl<R: DynamicRuntime> MessageSet for SupervisorMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
 {
+    fn source_id(&self) -> u16 {
+        match self {
+            SupervisorMessageSet::StandardMessage(msg) => msg.source_id(),
+            SupervisorMessageSet::SupervisorMessage(msg) => msg.source_id(),
+        }
+    }
+}
+
+impl<R: DynamicRuntime> fmt::Debug for SupervisorMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SupervisorMessageSet::StandardMessage(msg) => write!(
+                f,
+                "StandardMessage from {}: {:?}",
+                msg.source_id, msg.payload
+            ),
+            SupervisorMessageSet::SupervisorMessage(msg) => write!(
+                f,
+                "SupervisorMessage from {}: {:?}",
+                msg.source_id, msg.payload
+            ),
+        }
+    }
 }
 
 pub enum SupervisorPayload {
     //Spawn(Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> + Send + 'static>),
+    // Without alloc bloxes are spawned from static task pools instead
+    #[cfg(feature = "alloc")]
     Spawn(Pin<Box<dyn Future<Output = ()> + Send>>),
+    // Spawns a blox whose run future isn't Send, see Runtime::spawn_with
+    #[cfg(feature = "alloc")]
+    SpawnWith(SpawnMode, BloxFactory),
     RequestNewStandardHandle(usize),
-    Error(Box<String>),
+    // Sent by a blox whose state machine reached a top-level final state
+    Finished,
+    Error(ErrorString),
 }
 
 impl fmt::Debug for SupervisorPayload {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            #[cfg(feature = "alloc")]
             SupervisorPayload::Spawn(_) => write!(f, "Spawn"),
+            #[cfg(feature = "alloc")]
+            SupervisorPayload::SpawnWith(mode, _) => write!(f, "SpawnWith: {:?}", mode),
             SupervisorPayload::RequestNewStandardHandle(queue_size) => {
                 write!(f, "RequestNewStandardHandle: {}", queue_size)
             }
+            SupervisorPayload::Finished => write!(f, "Finished"),
             SupervisorPayload::Error(e) => write!(f, "Error: {}", e),
         }
     }
 }
 
+#[cfg(feature = "alloc")]
 pub enum SupervisorLocalPayload {
     SpawnLocal(Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()> + 'static>> + 'static>),
     RequestNewStandardHandle(usize),
-    Error(Box<String>),
+    Error(ErrorString),
 }
 
+#[cfg(feature = "alloc")]
 impl fmt::Debug for SupervisorLocalPayload {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -52,3 +92,21 @@ impl fmt::Debug for SupervisorLocalPayload {
         }
     }
 }
+
+impl<R: DynamicRuntime> From<Message<StandardPayload<R>>> for SupervisorMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+{
+    fn from(msg: Message<StandardPayload<R>>) -> Self {
+        SupervisorMessageSet::StandardMessage(msg)
+    }
+}
+
+impl<R: DynamicRuntime> From<Message<SupervisorPayload>> for SupervisorMessageSet<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+{
+    fn from(msg: Message<SupervisorPayload>) -> Self {
+        SupervisorMessageSet::SupervisorMessage(msg)
+    }
+}
@@ -1,10 +1,17 @@
 // Copyright 2025 Bloxide, all rights reserved
 
+// The supervisor spawns boxed futures and tracks bloxes in a map, so it needs alloc.
+// Its messages are always available so bloxes can report to it
+#[cfg(feature = "alloc")]
 pub mod components;
+#[cfg(feature = "alloc")]
 pub mod ext_state;
 pub mod messaging;
+#[cfg(feature = "alloc")]
 pub mod states;
 
+#[cfg(feature = "alloc")]
 pub use components::*;
+#[cfg(feature = "alloc")]
 pub use ext_state::*;
 pub use messaging::*;
@@ -6,7 +6,7 @@ use crate::state_machine::*;
 #[derive(Clone, PartialEq, Debug)]
 pub struct Error;
 
-impl<R: Runtime> State<SupervisorComponents<R>> for Error
+impl<R: DynamicRuntime> State<SupervisorComponents<R>> for Error
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
@@ -5,7 +5,7 @@ pub mod running;
 pub mod uninit;
 
 use super::{components::*, messaging::*};
-use crate::components::Runtime;
+use crate::components::DynamicRuntime;
 use crate::{messaging::*, state_machine::*};
 pub use {error::*, running::*, uninit::*};
 
@@ -21,9 +21,28 @@ impl Default for SupervisorStateEnum {
         SupervisorStateEnum::Uninit(Uninit)
     }
 }
-impl StateEnum for SupervisorStateEnum {}
+impl StateEnum for SupervisorStateEnum {
+    fn all_states() -> &'static [Self] {
+        &[
+            SupervisorStateEnum::Uninit(Uninit),
+            SupervisorStateEnum::Running(Running),
+            SupervisorStateEnum::Error(Error),
+        ]
+    }
+
+    fn transitions() -> &'static [(Self, Self)] {
+        &[(
+            SupervisorStateEnum::Running(Running),
+            SupervisorStateEnum::Error(Error),
+        )]
+    }
+
+    fn error_state() -> Option<Self> {
+        Some(SupervisorStateEnum::Error(Error))
+    }
+}
 
-impl<R: Runtime> State<SupervisorComponents<R>> for SupervisorStateEnum
+impl<R: DynamicRuntime> State<SupervisorComponents<R>> for SupervisorStateEnum
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
@@ -58,6 +77,64 @@ where
         }
     }
 
+    fn try_on_entry(
+        &self,
+        state_machine: &mut StateMachine<SupervisorComponents<R>>,
+    ) -> Result<(), StateError> {
+        match self {
+            SupervisorStateEnum::Uninit(s) => s.try_on_entry(state_machine),
+            SupervisorStateEnum::Running(s) => s.try_on_entry(state_machine),
+            SupervisorStateEnum::Error(s) => s.try_on_entry(state_machine),
+        }
+    }
+
+    fn try_on_exit(
+        &self,
+        state_machine: &mut StateMachine<SupervisorComponents<R>>,
+    ) -> Result<(), StateError> {
+        match self {
+            SupervisorStateEnum::Uninit(s) => s.try_on_exit(state_machine),
+            SupervisorStateEnum::Running(s) => s.try_on_exit(state_machine),
+            SupervisorStateEnum::Error(s) => s.try_on_exit(state_machine),
+        }
+    }
+
+    fn try_handle_message(
+        &self,
+        state_machine: &mut StateMachine<SupervisorComponents<R>>,
+        message: SupervisorMessageSet<R>,
+    ) -> HandlerResult<SupervisorStateEnum, SupervisorMessageSet<R>> {
+        match self {
+            SupervisorStateEnum::Uninit(s) => s.try_handle_message(state_machine, message),
+            SupervisorStateEnum::Running(s) => s.try_handle_message(state_machine, message),
+            SupervisorStateEnum::Error(s) => s.try_handle_message(state_machine, message),
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        match self {
+            SupervisorStateEnum::Uninit(s) => {
+                <Uninit as State<SupervisorComponents<R>>>::is_final(s)
+            }
+            SupervisorStateEnum::Running(s) => {
+                <Running as State<SupervisorComponents<R>>>::is_final(s)
+            }
+            SupervisorStateEnum::Error(s) => <Error as State<SupervisorComponents<R>>>::is_final(s),
+        }
+    }
+
+    fn on_completion(
+        &self,
+        state_machine: &mut StateMachine<SupervisorComponents<R>>,
+        final_state: &SupervisorStateEnum,
+    ) -> Option<SupervisorStateEnum> {
+        match self {
+            SupervisorStateEnum::Uninit(s) => s.on_completion(state_machine, final_state),
+            SupervisorStateEnum::Running(s) => s.on_completion(state_machine, final_state),
+            SupervisorStateEnum::Error(s) => s.on_completion(state_machine, final_state),
+        }
+    }
+
     fn parent(&self) -> SupervisorStateEnum {
         match self {
             SupervisorStateEnum::Uninit(s) => <Uninit as State<SupervisorComponents<R>>>::parent(s),
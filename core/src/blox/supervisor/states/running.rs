@@ -1,13 +1,13 @@
 // Copyright 2025 Bloxide, all rights reserved
 
 use super::*;
-use crate::{messaging::*, state_machine::*};
+use crate::{messaging::*, state_machine::*, std_exports::*};
 use log::*;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Running;
 
-impl<R: Runtime> State<SupervisorComponents<R>> for Running
+impl<R: DynamicRuntime> State<SupervisorComponents<R>> for Running
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
@@ -18,39 +18,70 @@ where
         SupervisorStateEnum::Uninit(Uninit)
     }
 
-    fn handle_message(
+    fn try_handle_message(
         &self,
         state_machine: &mut StateMachine<SupervisorComponents<R>>,
         message: SupervisorMessageSet<R>,
-    ) -> Option<Transition<SupervisorStateEnum, SupervisorMessageSet<R>>> {
+    ) -> HandlerResult<SupervisorStateEnum, SupervisorMessageSet<R>> {
         let transition = match message {
             SupervisorMessageSet::SupervisorMessage(message) => match message.payload {
                 SupervisorPayload::Spawn(future) => {
-                    let _ = state_machine.extended_state.spawn(future).map_err(|_| {
-                        error!("Failed to spawn blox");
-                    });
+                    state_machine
+                        .extended_state
+                        .spawn(future)
+                        .map_err(StateError::Other)?;
                     None
                 }
-                SupervisorPayload::RequestNewStandardHandle(queue_size) => {
-                    let (new_handle, rx) = state_machine
+                SupervisorPayload::SpawnWith(mode, factory) => {
+                    state_machine
                         .extended_state
-                        .request_new_standard_handle(queue_size);
+                        .spawn_with(mode, factory)
+                        .map_err(StateError::other)?;
+                    None
+                }
+                SupervisorPayload::RequestNewStandardHandle(queue_size) => {
+                    let requester = message.source_id();
                     let handle = state_machine
                         .extended_state
                         .blox
-                        .get(&message.source_id())
-                        .unwrap();
+                        .get(&requester)
+                        .cloned()
+                        .ok_or_else(|| {
+                            StateError::Other(format!("Unknown blox id {}", requester))
+                        })?;
+                    let (new_handle, rx) = state_machine
+                        .extended_state
+                        .request_new_standard_handle(queue_size);
+                    let id = new_handle.id();
                     let payload = StandardPayload::StandardChannel(new_handle, rx);
-                    let _ = handle.try_send(Message::new(
+                    if let Err(e) = handle.try_send(Message::new(
                         state_machine.self_handles.standard_handle.id(),
                         payload,
-                    ));
+                    )) {
+                        // Nobody received the handle, so no blox has this id
+                        state_machine.extended_state.blox.remove(&id);
+                        return Err(StateError::send(e));
+                    }
+                    state_machine.extended_state.parents.insert(id, requester);
+                    None
+                }
+                SupervisorPayload::Finished => {
+                    let id = message.source_id();
+                    info!("Blox {} finished", id);
+                    if let Some(parent) = state_machine.extended_state.remove_blox(id) {
+                        if let Err(e) = parent.try_send(Message::new(
+                            state_machine.self_handles.standard_handle.id(),
+                            StandardPayload::ChildFinished(id),
+                        )) {
+                            error!("Failed to send message: {:?}", e);
+                        }
+                    }
                     None
                 }
                 _ => None,
             },
             _ => None,
         };
-        transition
+        Ok(transition)
     }
 }
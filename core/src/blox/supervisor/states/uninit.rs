@@ -7,7 +7,7 @@ use log::*;
 #[derive(Clone, PartialEq, Debug)]
 pub struct Uninit;
 
-impl<R: Runtime> State<SupervisorComponents<R>> for Uninit
+impl<R: DynamicRuntime> State<SupervisorComponents<R>> for Uninit
 where
     R::MessageHandle<StandardPayload<R>>: Clone + Send + 'static,
     <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
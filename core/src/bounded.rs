@@ -0,0 +1,105 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Collections used by the core and the standard payloads.
+//!
+//! With the `alloc` feature these are the usual growable heap types.  Without it they
+//! are fixed-capacity `heapless` types stored inline, so bloxes need no global allocator.
+//! Pushing past the capacity fails instead of allocating.
+
+use core::fmt;
+
+/// Most states a blox can declare without alloc
+pub const MAX_STATES: usize = 32;
+
+/// Most internal events that can be posted while a message is handled without alloc
+pub const MAX_POSTED_EVENTS: usize = 8;
+
+/// Most handles a blox can keep in a `HandleVec` without alloc
+pub const MAX_HANDLES: usize = 8;
+
+/// Longest error message kept without alloc, longer messages are truncated
+pub const ERROR_STRING_CAPACITY: usize = 64;
+
+/// Largest raw payload carried without alloc
+pub const RAW_PAYLOAD_CAPACITY: usize = 256;
+
+#[cfg(feature = "alloc")]
+mod types {
+    use alloc::{collections::VecDeque, string::String, vec::Vec};
+
+    pub type StateVec<T> = Vec<T>;
+    pub type EventQueue<T> = VecDeque<T>;
+    pub type HandleVec<T> = Vec<T>;
+    pub type ErrorString = String;
+    pub type RawBytes = Vec<u8>;
+}
+
+#[cfg(not(feature = "alloc"))]
+mod types {
+    use super::*;
+
+    pub type StateVec<T> = heapless::Vec<T, MAX_STATES>;
+    pub type EventQueue<T> = heapless::Deque<T, MAX_POSTED_EVENTS>;
+    pub type HandleVec<T> = heapless::Vec<T, MAX_HANDLES>;
+    pub type ErrorString = heapless::String<ERROR_STRING_CAPACITY>;
+    pub type RawBytes = heapless::Vec<u8, RAW_PAYLOAD_CAPACITY>;
+}
+
+pub use types::*;
+
+/// Push that works the same on growable and fixed-capacity collections,
+/// returning the item when the collection is full
+pub trait BoundedPush<T> {
+    fn try_push(&mut self, item: T) -> Result<(), T>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> BoundedPush<T> for alloc::vec::Vec<T> {
+    fn try_push(&mut self, item: T) -> Result<(), T> {
+        self.push(item);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> BoundedPush<T> for alloc::collections::VecDeque<T> {
+    fn try_push(&mut self, item: T) -> Result<(), T> {
+        self.push_back(item);
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> BoundedPush<T> for heapless::Vec<T, N> {
+    fn try_push(&mut self, item: T) -> Result<(), T> {
+        self.push(item)
+    }
+}
+
+impl<T, const N: usize> BoundedPush<T> for heapless::Deque<T, N> {
+    fn try_push(&mut self, item: T) -> Result<(), T> {
+        self.push_back(item)
+    }
+}
+
+/// Formats an `ErrorString`, truncating it when it doesn't fit
+pub fn error_string(args: fmt::Arguments<'_>) -> ErrorString {
+    let mut s = ErrorString::new();
+    let _ = fmt::Write::write_fmt(&mut Truncating(&mut s), args);
+    s
+}
+
+// A fixed-capacity string rejects a fragment that doesn't fit as a whole, so the one
+// that overflows is written a char at a time, keeping as much of it as fits
+struct Truncating<'a>(&'a mut ErrorString);
+
+impl fmt::Write for Truncating<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if fmt::Write::write_str(self.0, s).is_ok() {
+            return Ok(());
+        }
+        for c in s.chars() {
+            fmt::Write::write_char(self.0, c)?;
+        }
+        Ok(())
+    }
+}
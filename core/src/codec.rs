@@ -0,0 +1,192 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Encoding typed payloads to the bytes of a `RawPayload`, and back.
+//!
+//! The bytes start with the payload type's wire tag, two bytes little-endian, followed
+//! by the payload encoded with a `Codec`.  Tags are assigned to payload types with
+//! `wire_tags!`, so the receiving side can tell what it was sent with `wire_tag` before
+//! decoding.  Codecs are enabled with the `codec-postcard`, `codec-cbor` and
+//! `codec-json` features.
+
+use crate::{messaging::*, std_exports::*};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A serialization format for payloads
+pub trait Codec {
+    /// Appends `value`, encoded, to `out`
+    fn encode<T: Serialize>(value: &T, out: &mut RawBytes) -> Result<(), CodecError>;
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError>;
+}
+
+/// A payload type with a wire tag, implemented with `wire_tags!`
+pub trait WirePayload: Serialize + DeserializeOwned {
+    const WIRE_TAG: u16;
+}
+
+/// Assigns wire tags to payload types, checking at compile time that no two types in
+/// one invocation share a tag.
+///
+/// ```ignore
+/// wire_tags! {
+///     CounterPayload = 1,
+///     ThermostatPayload = 2,
+/// }
+/// ```
+#[macro_export]
+macro_rules! wire_tags {
+    ($($payload:ty = $tag:expr),+ $(,)?) => {
+        $(
+            impl $crate::codec::WirePayload for $payload {
+                const WIRE_TAG: u16 = $tag;
+            }
+        )+
+        const _: () = {
+            let tags: &[u16] = &[$($tag),+];
+            let mut i = 0;
+            while i < tags.len() {
+                let mut j = i + 1;
+                while j < tags.len() {
+                    assert!(tags[i] != tags[j], "Duplicate wire tag");
+                    j += 1;
+                }
+                i += 1;
+            }
+        };
+    };
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodecError {
+    Encode(ErrorString),
+    Decode(ErrorString),
+    /// The bytes are too short to hold a wire tag
+    MissingTag,
+    /// The bytes hold a payload of another type
+    WrongTag {
+        expected: u16,
+        found: u16,
+    },
+    /// The encoded payload doesn't fit in `RawBytes`, only without alloc
+    TooLarge,
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::Encode(e) => write!(f, "Failed to encode payload: {}", e),
+            CodecError::Decode(e) => write!(f, "Failed to decode payload: {}", e),
+            CodecError::MissingTag => write!(f, "Payload has no wire tag"),
+            CodecError::WrongTag { expected, found } => {
+                write!(f, "Expected wire tag {}, found {}", expected, found)
+            }
+            CodecError::TooLarge => write!(f, "Encoded payload is too large"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CodecError {}
+
+/// The wire tag of encoded payload bytes
+pub fn wire_tag(bytes: &[u8]) -> Option<u16> {
+    match bytes {
+        [low, high, ..] => Some(u16::from_le_bytes([*low, *high])),
+        _ => None,
+    }
+}
+
+fn push_bytes(out: &mut RawBytes, bytes: &[u8]) -> Result<(), CodecError> {
+    for byte in bytes {
+        out.try_push(*byte).map_err(|_| CodecError::TooLarge)?;
+    }
+    Ok(())
+}
+
+impl<P: WirePayload> Message<P> {
+    /// Encodes the message for the blox `to`, tagged with the payload's wire tag
+    pub fn to_raw<C: Codec>(&self, to: u16) -> Result<RawPayload, CodecError> {
+        let mut payload = RawBytes::new();
+        push_bytes(&mut payload, &P::WIRE_TAG.to_le_bytes())?;
+        C::encode(&self.payload, &mut payload)?;
+        Ok(RawPayload {
+            to,
+            from: self.source_id,
+            payload,
+        })
+    }
+
+    /// Decodes a message encoded with `to_raw`, failing if it holds another payload type
+    pub fn from_raw<C: Codec>(raw: &RawPayload) -> Result<Self, CodecError> {
+        let found = wire_tag(&raw.payload).ok_or(CodecError::MissingTag)?;
+        if found != P::WIRE_TAG {
+            return Err(CodecError::WrongTag {
+                expected: P::WIRE_TAG,
+                found,
+            });
+        }
+        let payload = C::decode(&raw.payload[2..])?;
+        Ok(Message::new(raw.from, payload))
+    }
+}
+
+/// Compact binary encoding with postcard, works without std or alloc
+#[cfg(feature = "codec-postcard")]
+pub struct Postcard;
+
+#[cfg(feature = "codec-postcard")]
+impl Codec for Postcard {
+    #[cfg(feature = "alloc")]
+    fn encode<T: Serialize>(value: &T, out: &mut RawBytes) -> Result<(), CodecError> {
+        let encoded = postcard::to_extend(value, Vec::new())
+            .map_err(|e| CodecError::Encode(error_string(format_args!("{}", e))))?;
+        out.extend_from_slice(&encoded);
+        Ok(())
+    }
+
+    // Encoded on the stack first, as extending a full `heapless::Vec` panics
+    #[cfg(not(feature = "alloc"))]
+    fn encode<T: Serialize>(value: &T, out: &mut RawBytes) -> Result<(), CodecError> {
+        let mut buffer = [0u8; RAW_PAYLOAD_CAPACITY];
+        let encoded = postcard::to_slice(value, &mut buffer).map_err(|e| match e {
+            postcard::Error::SerializeBufferFull => CodecError::TooLarge,
+            e => CodecError::Encode(error_string(format_args!("{}", e))),
+        })?;
+        push_bytes(out, encoded)
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+        postcard::from_bytes(bytes)
+            .map_err(|e| CodecError::Decode(error_string(format_args!("{}", e))))
+    }
+}
+
+/// CBOR encoding with ciborium
+#[cfg(feature = "codec-cbor")]
+pub struct Cbor;
+
+#[cfg(feature = "codec-cbor")]
+impl Codec for Cbor {
+    fn encode<T: Serialize>(value: &T, out: &mut RawBytes) -> Result<(), CodecError> {
+        ciborium::into_writer(value, out).map_err(|e| CodecError::Encode(format!("{}", e)))
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+        ciborium::from_reader(bytes).map_err(|e| CodecError::Decode(format!("{}", e)))
+    }
+}
+
+/// JSON encoding with serde_json, readable on the wire
+#[cfg(feature = "codec-json")]
+pub struct Json;
+
+#[cfg(feature = "codec-json")]
+impl Codec for Json {
+    fn encode<T: Serialize>(value: &T, out: &mut RawBytes) -> Result<(), CodecError> {
+        serde_json::to_writer(out, value).map_err(|e| CodecError::Encode(format!("{}", e)))
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+        serde_json::from_slice(bytes).map_err(|e| CodecError::Decode(format!("{}", e)))
+    }
+}
@@ -1,12 +1,14 @@
 // Copyright 2025 Bloxide, all rights reserved
 
-use crate::{blox::supervisor::messaging::*, messaging::*, state_machine::*, std_exports::*};
+#[cfg(feature = "alloc")]
+use crate::blox::supervisor::messaging::*;
+use crate::{messaging::*, state_machine::*, std_exports::*};
 use futures_core::stream::Stream;
 
 // A trait to encapsulate types needed for a blox
 pub trait Components {
     type ExtendedState: ExtendedState;
-    type States: StateEnum + Default;
+    type States: StateEnum + Default + 'static;
     type MessageSet: MessageSet;
     type Receivers;
     type Handles;
@@ -38,10 +40,12 @@ where
 }
 
 //Implement Runnable or RunnableLocal depending on if the blox implements Send
+#[cfg(feature = "alloc")]
 pub trait Runnable<B: Components> {
     fn run(self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
 }
 
+#[cfg(feature = "alloc")]
 pub trait RunnableLocal<B: Components> {
     fn run_local(self: Box<Self>) -> Pin<Box<dyn Future<Output = ()> + 'static>>;
     fn into_request(self: Box<Self>) -> SupervisorLocalPayload
@@ -55,6 +59,53 @@ pub trait RunnableLocal<B: Components> {
 
         SupervisorLocalPayload::SpawnLocal(Box::new(closure))
     }
+
+    /// Asks the supervisor to spawn this blox with `mode`, for bloxes that can be moved
+    /// between threads but whose run future isn't `Send`
+    fn into_spawn_request(self: Box<Self>, mode: SpawnMode) -> SupervisorPayload
+    where
+        Self: Send + 'static,
+    {
+        SupervisorPayload::SpawnWith(mode, Box::new(move || self.run_local()))
+    }
+}
+
+/// Builds a blox's run future on the thread it will run on, so the future doesn't
+/// need to be `Send`
+#[cfg(feature = "alloc")]
+pub type BloxFactory = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()> + 'static>> + Send>;
+
+/// Where a blox whose run future isn't `Send` is spawned, see `Runtime::spawn_with`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnMode {
+    /// On the runtime's local executor, on the thread of the blox that spawns it
+    Local,
+    /// On a new thread with its own single-threaded executor
+    Dedicated,
+}
+
+/// Error returned by `Runtime::spawn_with`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpawnError {
+    /// The runtime can't spawn bloxes with this mode
+    Unsupported(SpawnMode),
+    /// Spawning failed, holds the reason
+    Failed(ErrorString),
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::Unsupported(mode) => write!(f, "Spawn mode {:?} not supported", mode),
+            SpawnError::Failed(e) => write!(f, "Spawn failed: {}", e),
+        }
+    }
+}
+
+/// Runs a blox by value without boxing it, for statically allocated bloxes.
+/// The returned future can be spawned on a static task pool, such as an embassy task.
+pub trait RunnableStatic<B: Components> {
+    fn run_static(self) -> impl Future<Output = ()> + Send + 'static;
 }
 
 /// Core trait that each runtime must implement.
@@ -73,4 +124,18 @@ pub trait Runtime: Clone + Send + 'static {
     fn to_stream<P: Send + 'static>(
         receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
     ) -> Self::ReceiverStream<P>;
+
+    /// Spawns a blox whose run future isn't `Send`, built by `factory` where it will run.
+    /// Runtimes support none of the modes unless they override this.
+    #[cfg(feature = "alloc")]
+    fn spawn_with(mode: SpawnMode, factory: BloxFactory) -> Result<(), SpawnError> {
+        drop(factory);
+        Err(SpawnError::Unsupported(mode))
+    }
 }
+
+/// A runtime that can spawn futures and create channels while bloxes run.  Bloxes that
+/// do either, like the supervisor, or that start do-activities, require one.  Runtimes
+/// without it, such as an allocation-free one with static channels and task pools,
+/// panic in `Runtime::spawn` and `MessageSender::create_channel_with_size`.
+pub trait DynamicRuntime: Runtime {}
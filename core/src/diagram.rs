@@ -0,0 +1,302 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! State diagrams of a blox, rendered as Mermaid, PlantUML or Graphviz DOT.
+//!
+//! The hierarchy comes from `StateEnum::all_states` and `State::parent`, transitions
+//! from `StateEnum::transitions` and from any runs observed with a `TransitionCollector`.
+//! A transition out of the root state is drawn as the initial transition, and final
+//! states are marked as such.
+
+use crate::{components::*, observer::Observer, state_machine::*, std_exports::*};
+use core::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramFormat {
+    Mermaid,
+    PlantUml,
+    Dot,
+}
+
+/// The states, hierarchy and transitions of a blox, ready to be rendered
+pub struct StateDiagram<S: 'static> {
+    name: String,
+    states: &'static [S],
+    // Index of each state's parent, the root is its own parent
+    parents: Vec<usize>,
+    is_final: Vec<bool>,
+    transitions: Vec<(usize, usize)>,
+}
+
+impl<S: PartialEq + fmt::Debug + 'static> StateDiagram<S> {
+    /// Diagram of the blox with components `C`, with its declared transitions
+    pub fn new<C>(name: &str) -> Self
+    where
+        C: Components<States = S>,
+        S: State<C> + StateEnum,
+    {
+        let states = S::all_states();
+        let parents = states
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let parent = s.parent();
+                states.iter().position(|p| *p == parent).unwrap_or(i)
+            })
+            .collect();
+        let is_final = states.iter().map(|s| s.is_final()).collect();
+        let diagram = Self {
+            name: name.to_string(),
+            states,
+            parents,
+            is_final,
+            transitions: Vec::new(),
+        };
+        diagram.with_transitions(S::transitions().iter().map(|(from, to)| (from, to)))
+    }
+
+    /// Adds the transition into the blox's entry point, the state `init` is given
+    pub fn with_initial(mut self, state: &S) -> Self {
+        let root = self.root();
+        let state = self.index_of(state);
+        // Listed first, where readers look for it
+        self.transitions.retain(|t| *t != (root, state));
+        self.transitions.insert(0, (root, state));
+        self
+    }
+
+    /// Adds transitions, such as those gathered by a `TransitionCollector`.
+    /// Duplicates and transitions between unknown states are left out.
+    pub fn with_transitions<'a>(
+        mut self,
+        transitions: impl IntoIterator<Item = (&'a S, &'a S)>,
+    ) -> Self {
+        for (from, to) in transitions {
+            let (Some(from), Some(to)) = (self.position(from), self.position(to)) else {
+                continue;
+            };
+            self = self.with_index_transition(from, to);
+        }
+        self
+    }
+
+    pub fn render(&self, format: DiagramFormat) -> String {
+        match format {
+            DiagramFormat::Mermaid => self.mermaid(),
+            DiagramFormat::PlantUml => self.plantuml(),
+            DiagramFormat::Dot => self.dot(),
+        }
+    }
+
+    pub fn mermaid(&self) -> String {
+        let mut out = String::from("---\n");
+        let _ = writeln!(out, "title: {}\n---\nstateDiagram-v2", self.name);
+        self.write_nested(&mut out, self.root(), 1, &|out, indent, name, composite| {
+            if composite {
+                let _ = writeln!(out, "{}state {} {{", indent, name);
+            } else {
+                let _ = writeln!(out, "{}{}", indent, name);
+            }
+        });
+        self.write_edges(&mut out);
+        out
+    }
+
+    pub fn plantuml(&self) -> String {
+        let mut out = String::from("@startuml\n");
+        let _ = writeln!(out, "title {}", self.name);
+        self.write_nested(&mut out, self.root(), 1, &|out, indent, name, composite| {
+            let open = if composite { " {" } else { "" };
+            let _ = writeln!(out, "{}state {}{}", indent, name, open);
+        });
+        self.write_edges(&mut out);
+        out.push_str("@enduml\n");
+        out
+    }
+
+    pub fn dot(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph \"{}\" {{", self.name);
+        out.push_str("    compound=true;\n    node [shape=box, style=rounded];\n");
+        out.push_str("    \"[*]\" [shape=point];\n");
+        self.write_dot_children(&mut out, self.root(), 1);
+        let root = self.root();
+        for &(from, to) in self.transitions.iter() {
+            let from_name = if from == root {
+                "[*]".to_string()
+            } else {
+                self.name_of(from)
+            };
+            let mut attributes = Vec::new();
+            if self.is_composite(from) && from != root {
+                attributes.push(format!("ltail=\"cluster_{}\"", from_name));
+            }
+            if self.is_composite(to) {
+                attributes.push(format!("lhead=\"cluster_{}\"", self.name_of(to)));
+            }
+            let attributes = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            let _ = writeln!(
+                out,
+                "    \"{}\" -> \"{}\"{};",
+                from_name,
+                self.name_of(to),
+                attributes
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn with_index_transition(mut self, from: usize, to: usize) -> Self {
+        if !self.transitions.contains(&(from, to)) {
+            self.transitions.push((from, to));
+        }
+        self
+    }
+
+    fn root(&self) -> usize {
+        (0..self.states.len())
+            .find(|&i| self.parents[i] == i)
+            .unwrap_or_default()
+    }
+
+    fn position(&self, state: &S) -> Option<usize> {
+        self.states.iter().position(|s| s == state)
+    }
+
+    fn index_of(&self, state: &S) -> usize {
+        self.position(state)
+            .unwrap_or_else(|| panic!("State {:?} is not listed in all_states", state))
+    }
+
+    fn children(&self, parent: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.states.len()).filter(move |&i| i != parent && self.parents[i] == parent)
+    }
+
+    fn is_composite(&self, state: usize) -> bool {
+        self.children(state).next().is_some()
+    }
+
+    fn name_of(&self, state: usize) -> String {
+        state_name(&self.states[state])
+    }
+
+    // Writes the states below `parent`, `open` writes a state's line and opens a block
+    // for composite states, closed here with `}`
+    fn write_nested(
+        &self,
+        out: &mut String,
+        parent: usize,
+        depth: usize,
+        open: &dyn Fn(&mut String, &str, &str, bool),
+    ) {
+        let indent = "    ".repeat(depth);
+        for child in self.children(parent) {
+            let composite = self.is_composite(child);
+            open(out, &indent, &self.name_of(child), composite);
+            if composite {
+                self.write_nested(out, child, depth + 1, open);
+                let _ = writeln!(out, "{}}}", indent);
+            }
+        }
+    }
+
+    // Writes transitions, those out of the root as initial ones, and final states in the
+    // syntax shared by Mermaid and PlantUML
+    fn write_edges(&self, out: &mut String) {
+        let root = self.root();
+        for &(from, to) in self.transitions.iter() {
+            let from = if from == root {
+                "[*]".to_string()
+            } else {
+                self.name_of(from)
+            };
+            let _ = writeln!(out, "    {} --> {}", from, self.name_of(to));
+        }
+        for state in (0..self.states.len()).filter(|&i| self.is_final[i]) {
+            let _ = writeln!(out, "    {} --> [*]", self.name_of(state));
+        }
+    }
+
+    fn write_dot_children(&self, out: &mut String, parent: usize, depth: usize) {
+        let indent = "    ".repeat(depth);
+        for child in self.children(parent) {
+            let name = self.name_of(child);
+            if self.is_composite(child) {
+                let _ = writeln!(out, "{}subgraph \"cluster_{}\" {{", indent, name);
+                let _ = writeln!(out, "{}    label=\"{}\";", indent, name);
+                // Edges to and from the composite are drawn to this invisible anchor
+                let _ = writeln!(
+                    out,
+                    "{}    \"{}\" [shape=point, style=invis];",
+                    indent, name
+                );
+                self.write_dot_children(out, child, depth + 1);
+                let _ = writeln!(out, "{}}}", indent);
+            } else if self.is_final[child] {
+                let _ = writeln!(out, "{}\"{}\" [peripheries=2];", indent, name);
+            } else {
+                let _ = writeln!(out, "{}\"{}\";", indent, name);
+            }
+        }
+    }
+}
+
+// `Variant(Variant)`, the usual form of a state, is shortened to `Variant`, other
+// states are named by their `Debug` output with punctuation replaced
+pub(crate) fn state_name(state: &impl fmt::Debug) -> String {
+    let debug = format!("{:?}", state);
+    if let Some((variant, rest)) = debug.split_once('(') {
+        if rest.strip_suffix(')') == Some(variant) {
+            return variant.to_string();
+        }
+    }
+    let name: String = debug
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    name.trim_matches('_').to_string()
+}
+
+/// Observer gathering the transitions a state machine takes, to be added to a
+/// `StateDiagram` with `with_transitions`
+#[derive(Clone)]
+pub struct TransitionCollector<S> {
+    transitions: Arc<Mutex<Vec<(S, S)>>>,
+}
+
+impl<S> Default for TransitionCollector<S> {
+    fn default() -> Self {
+        Self {
+            transitions: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl<S: Clone + PartialEq> TransitionCollector<S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The distinct transitions seen so far, in the order first seen
+    pub fn transitions(&self) -> Vec<(S, S)> {
+        self.transitions.lock().unwrap().clone()
+    }
+}
+
+impl<C> Observer<C> for TransitionCollector<C::States>
+where
+    C: Components,
+    C::States: Clone + PartialEq + Send,
+{
+    fn on_transition(&mut self, from: &C::States, to: &C::States) {
+        let mut transitions = self.transitions.lock().unwrap();
+        if !transitions.iter().any(|(f, t)| f == from && t == to) {
+            transitions.push((from.clone(), to.clone()));
+        }
+    }
+}
@@ -1,37 +1,62 @@
 // Copyright 2025 Bloxide, all rights reserved
 
-#![cfg_attr(feature = "runtime-embassy", no_std)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+pub mod activity;
 pub mod blox;
+pub mod bounded;
+pub mod codec;
 pub mod components;
+#[cfg(feature = "std")]
+pub mod diagram;
 pub mod macros;
 pub mod merge;
 pub mod messaging;
+#[cfg(feature = "std")]
+pub mod metrics;
+pub mod observer;
+#[cfg(feature = "record")]
+pub mod record;
+pub mod spans;
 pub mod state_machine;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod unhandled;
 // Core re-exports
 pub use crate::{components::*, messaging::*};
 
 pub use blox::supervisor::*;
 
-// Conditional type exports
-
-pub mod std_exports {
-    pub use crate::common_exports::*;
-    //pub use tokio::sync::mpsc::error::TrySendError;
-}
+// Conditional type exports, everything here is available with or without std
 
-#[cfg(feature = "runtime-embassy")]
 pub mod std_exports {
+    #[cfg(feature = "alloc")]
+    pub use crate::alloc_exports::*;
+    pub use crate::bounded::*;
     pub use crate::common_exports::*;
+    #[cfg(feature = "runtime-embassy")]
     pub use embassy_sync::channel::TrySendError;
+    #[cfg(feature = "runtime-embassy")]
     pub use embassy_sync::once_lock::OnceLock;
 }
 
-pub mod common_exports {
-    extern crate alloc;
+#[cfg(feature = "alloc")]
+pub mod alloc_exports {
+    pub use alloc::borrow::ToOwned;
     pub use alloc::boxed::Box;
+    pub use alloc::collections::VecDeque;
     pub use alloc::string::String;
+    pub use alloc::string::ToString;
     pub use alloc::vec::Vec;
+    pub use alloc::{format, vec};
+    pub use hashbrown::HashMap;
+}
+
+pub mod common_exports {
     pub use core::any::Any;
     pub use core::cell::LazyCell;
     pub use core::cell::OnceCell;
@@ -41,7 +66,6 @@ pub mod common_exports {
     pub use core::hash::Hasher;
     pub use core::marker::PhantomData;
     pub use core::pin::Pin;
-    pub use hashbrown::HashMap;
 }
 
 // Re-export everything from std_exports at crate root
@@ -5,7 +5,7 @@ use crate::std_exports::*;
 use serde::{Deserialize, Serialize};
 
 /// Basic message type that wraps any payload and has an id
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message<P> {
     pub source_id: u16,
     pub payload: P,
@@ -25,11 +25,24 @@ impl<P> Message<P> {
 pub struct RawPayload {
     pub to: u16,
     pub from: u16,
-    pub payload: Vec<u8>,
+    pub payload: RawBytes,
 }
 
-/// Marker trait for message sets
-pub trait MessageSet {}
+/// Trait for message sets, the enum of every message a blox can receive
+pub trait MessageSet: fmt::Debug {
+    /// Id of the blox that sent the message
+    fn source_id(&self) -> u16;
+}
+
+/// Why a `try_send` failed, independent of the runtime's error type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendErrorKind {
+    /// The channel is at capacity
+    Full,
+    /// The receiver has been dropped
+    Closed,
+    Other,
+}
 
 /// Trait for handles to send messages
 pub trait MessageSender {
@@ -39,6 +52,11 @@ pub trait MessageSender {
     type ErrorType: fmt::Debug;
     fn try_send(&self, msg: Message<Self::PayloadType>) -> Result<(), Self::ErrorType>;
 
+    /// Classifies an error returned by `try_send`
+    fn error_kind(_error: &Self::ErrorType) -> SendErrorKind {
+        SendErrorKind::Other
+    }
+
     fn id(&self) -> u16;
 
     fn create_channel_with_size(id: u16, size: usize) -> (Self, Self::ReceiverType)
@@ -53,14 +71,46 @@ where
 {
     Shutdown,
     PollHandle,
+    #[cfg(feature = "alloc")]
     Handle(Box<dyn Any + Send>),
     PollState,
+    #[cfg(feature = "alloc")]
     State(Box<dyn Any + Send>),
-    Error(Box<String>),
+    Error(ErrorString),
     StandardChannel(
         R::MessageHandle<StandardPayload<R>>,
         <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType,
     ),
-    RawInbound(u16, Vec<u8>),
-    RawOutbound(u16, Vec<u8>),
+    // Sent by the supervisor when a blox it spawned on our behalf has finished
+    ChildFinished(u16),
+    RawInbound(u16, RawBytes),
+    RawOutbound(u16, RawBytes),
+}
+
+impl<R: Runtime> fmt::Debug for StandardPayload<R>
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StandardPayload::Shutdown => write!(f, "Shutdown"),
+            StandardPayload::PollHandle => write!(f, "PollHandle"),
+            #[cfg(feature = "alloc")]
+            StandardPayload::Handle(_) => write!(f, "Handle"),
+            StandardPayload::PollState => write!(f, "PollState"),
+            #[cfg(feature = "alloc")]
+            StandardPayload::State(_) => write!(f, "State"),
+            StandardPayload::Error(e) => write!(f, "Error: {}", e),
+            StandardPayload::StandardChannel(handle, _) => {
+                write!(f, "StandardChannel: {}", handle.id())
+            }
+            StandardPayload::ChildFinished(id) => write!(f, "ChildFinished: {}", id),
+            StandardPayload::RawInbound(from, payload) => {
+                write!(f, "RawInbound: {} ({} bytes)", from, payload.len())
+            }
+            StandardPayload::RawOutbound(to, payload) => {
+                write!(f, "RawOutbound: {} ({} bytes)", to, payload.len())
+            }
+        }
+    }
 }
@@ -0,0 +1,492 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Metrics of bloxes and their channels, reported to a pluggable `MetricsSink`.
+//!
+//! A `MetricsObserver` added to a blox's state machine reports how long each message
+//! took to handle and the transitions taken.  Channels are metered by running bloxes on
+//! `Metered<R>` instead of the runtime `R`, which reports messages received, mailbox
+//! depth and `try_send` failures.  Observers are given their sink, but channels are
+//! created by `MessageSender::create_channel_with_size`, where there is nothing to pass
+//! one through, so they report to the global sink installed with `set_sink`.  Channels
+//! created with `MeteredHandle::create_channel_with_sink` report to their own sink.
+//! `InMemoryMetrics` keeps everything reported and renders it in the Prometheus text
+//! format.
+
+use crate::{components::*, diagram::state_name, messaging::*, observer::Observer, std_exports::*};
+use core::task::{Context, Poll};
+use futures_core::Stream;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+
+/// Seconds taken to handle a message, by blox and the state that received it
+pub const DISPATCH_SECONDS: &str = "bloxide_dispatch_seconds";
+/// Transitions taken, by blox and the states they went from and to
+pub const TRANSITIONS_TOTAL: &str = "bloxide_transitions_total";
+/// Messages taken from a channel, by channel id
+pub const MESSAGES_RECEIVED_TOTAL: &str = "bloxide_messages_received_total";
+/// Messages sent and not yet received, by channel id
+pub const MAILBOX_DEPTH: &str = "bloxide_mailbox_depth";
+/// Failed `try_send`s, by channel id and `SendErrorKind`
+pub const SEND_FAILURES_TOTAL: &str = "bloxide_send_failures_total";
+
+/// Upper bounds of the histogram buckets kept by `InMemoryMetrics`, in seconds
+pub const HISTOGRAM_BUCKETS: &[f64] = &[1e-6, 1e-5, 1e-4, 1e-3, 1e-2, 0.1, 1.0];
+
+/// Labels of a metric, `(name, value)`
+pub type Labels<'a> = &'a [(&'static str, &'a str)];
+
+/// Where metrics are reported.  Called on the bloxes' threads, from within dispatches
+/// and sends, so implementations should be quick.
+pub trait MetricsSink: Send + Sync {
+    fn increment_counter(&self, name: &'static str, labels: Labels, value: u64);
+
+    fn set_gauge(&self, name: &'static str, labels: Labels, value: f64);
+
+    fn record_histogram(&self, name: &'static str, labels: Labels, value: f64);
+}
+
+static SINK: RwLock<Option<Arc<dyn MetricsSink>>> = RwLock::new(None);
+
+/// Sets the sink `Metered` channels report to, or stops them reporting with `None`
+pub fn set_sink(sink: Option<Arc<dyn MetricsSink>>) {
+    *SINK.write().unwrap() = sink;
+}
+
+fn with_sink(f: impl FnOnce(&dyn MetricsSink)) {
+    if let Some(sink) = SINK.read().unwrap().as_deref() {
+        f(sink);
+    }
+}
+
+/// Observer reporting the dispatch latency and transitions of one blox
+pub struct MetricsObserver {
+    blox: String,
+    sink: Arc<dyn MetricsSink>,
+    // When the message being dispatched was received, and the state it was received in
+    received: Option<(Instant, String)>,
+}
+
+impl MetricsObserver {
+    /// Reports to `sink` with the label `blox` set to `blox`
+    pub fn new(blox: &str, sink: Arc<dyn MetricsSink>) -> Self {
+        Self {
+            blox: blox.to_string(),
+            sink,
+            received: None,
+        }
+    }
+}
+
+impl<C: Components> Observer<C> for MetricsObserver {
+    fn on_receive(&mut self, state: &C::States, _message: &C::MessageSet) {
+        self.received = Some((Instant::now(), state_name(state)));
+    }
+
+    fn on_dispatched(&mut self, _state: &C::States) {
+        let Some((received, state)) = self.received.take() else {
+            return;
+        };
+        self.sink.record_histogram(
+            DISPATCH_SECONDS,
+            &[("blox", &self.blox), ("state", &state)],
+            received.elapsed().as_secs_f64(),
+        );
+    }
+
+    fn on_transition(&mut self, from: &C::States, to: &C::States) {
+        self.sink.increment_counter(
+            TRANSITIONS_TOTAL,
+            &[
+                ("blox", &self.blox),
+                ("from", &state_name(from)),
+                ("to", &state_name(to)),
+            ],
+            1,
+        );
+    }
+}
+
+/// A histogram kept by `InMemoryMetrics`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Number of values at most each of `HISTOGRAM_BUCKETS`, not cumulative
+    pub buckets: Vec<u64>,
+    pub count: u64,
+    pub sum: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricValue {
+    Counter(u64),
+    Gauge(f64),
+    Histogram(Histogram),
+}
+
+type MetricKey = (&'static str, Vec<(&'static str, String)>);
+
+/// Sink keeping every metric reported, to be read back or exported with `prometheus`
+#[derive(Default)]
+pub struct InMemoryMetrics {
+    metrics: Mutex<BTreeMap<MetricKey, MetricValue>>,
+}
+
+impl InMemoryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value of a metric, with its labels in the order they were reported
+    pub fn get(&self, name: &str, labels: Labels) -> Option<MetricValue> {
+        let metrics = self.metrics.lock().unwrap();
+        metrics
+            .iter()
+            .find(|((n, l), _)| {
+                *n == name
+                    && l.len() == labels.len()
+                    && l.iter().zip(labels).all(|(a, b)| a.0 == b.0 && a.1 == b.1)
+            })
+            .map(|(_, value)| value.clone())
+    }
+
+    /// The value of a counter, 0 if it was never incremented
+    pub fn counter(&self, name: &str, labels: Labels) -> u64 {
+        match self.get(name, labels) {
+            Some(MetricValue::Counter(value)) => value,
+            _ => 0,
+        }
+    }
+
+    pub fn gauge(&self, name: &str, labels: Labels) -> Option<f64> {
+        match self.get(name, labels) {
+            Some(MetricValue::Gauge(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn histogram(&self, name: &str, labels: Labels) -> Option<Histogram> {
+        match self.get(name, labels) {
+            Some(MetricValue::Histogram(histogram)) => Some(histogram),
+            _ => None,
+        }
+    }
+
+    /// Every metric in the Prometheus text exposition format
+    pub fn prometheus(&self) -> String {
+        let metrics = self.metrics.lock().unwrap();
+        let mut out = String::new();
+        let mut previous = None;
+        for ((name, labels), value) in metrics.iter() {
+            if previous != Some(*name) {
+                let kind = match value {
+                    MetricValue::Counter(_) => "counter",
+                    MetricValue::Gauge(_) => "gauge",
+                    MetricValue::Histogram(_) => "histogram",
+                };
+                out.push_str(&format!("# TYPE {} {}\n", name, kind));
+                previous = Some(*name);
+            }
+            match value {
+                MetricValue::Counter(value) => {
+                    out.push_str(&format!("{}{} {}\n", name, label_set(labels, None), value));
+                }
+                MetricValue::Gauge(value) => {
+                    out.push_str(&format!("{}{} {}\n", name, label_set(labels, None), value));
+                }
+                MetricValue::Histogram(histogram) => {
+                    let mut cumulative = 0;
+                    for (bound, count) in HISTOGRAM_BUCKETS.iter().zip(&histogram.buckets) {
+                        cumulative += count;
+                        let le = bound.to_string();
+                        out.push_str(&format!(
+                            "{}_bucket{} {}\n",
+                            name,
+                            label_set(labels, Some(&le)),
+                            cumulative
+                        ));
+                    }
+                    out.push_str(&format!(
+                        "{}_bucket{} {}\n",
+                        name,
+                        label_set(labels, Some("+Inf")),
+                        histogram.count
+                    ));
+                    let labels = label_set(labels, None);
+                    out.push_str(&format!("{}_sum{} {}\n", name, labels, histogram.sum));
+                    out.push_str(&format!("{}_count{} {}\n", name, labels, histogram.count));
+                }
+            }
+        }
+        out
+    }
+
+    fn update(&self, name: &'static str, labels: Labels, f: impl FnOnce(&mut MetricValue)) {
+        let key = (
+            name,
+            labels.iter().map(|(n, v)| (*n, v.to_string())).collect(),
+        );
+        let mut metrics = self.metrics.lock().unwrap();
+        // Placeholder replaced by `f` for metrics seen for the first time
+        f(metrics.entry(key).or_insert(MetricValue::Counter(0)));
+    }
+}
+
+// `{name="value",...}`, with `le` last for histogram buckets
+fn label_set(labels: &[(&'static str, String)], le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{}\"", le));
+    }
+    if pairs.is_empty() {
+        return String::new();
+    }
+    format!("{{{}}}", pairs.join(","))
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl MetricsSink for InMemoryMetrics {
+    fn increment_counter(&self, name: &'static str, labels: Labels, value: u64) {
+        self.update(name, labels, |metric| match metric {
+            MetricValue::Counter(count) => *count += value,
+            other => *other = MetricValue::Counter(value),
+        });
+    }
+
+    fn set_gauge(&self, name: &'static str, labels: Labels, value: f64) {
+        self.update(name, labels, |metric| *metric = MetricValue::Gauge(value));
+    }
+
+    fn record_histogram(&self, name: &'static str, labels: Labels, value: f64) {
+        self.update(name, labels, |metric| {
+            if !matches!(metric, MetricValue::Histogram(_)) {
+                *metric = MetricValue::Histogram(Histogram {
+                    buckets: vec![0; HISTOGRAM_BUCKETS.len()],
+                    count: 0,
+                    sum: 0.0,
+                });
+            }
+            let MetricValue::Histogram(histogram) = metric else {
+                unreachable!()
+            };
+            if let Some(bucket) = HISTOGRAM_BUCKETS.iter().position(|b| value <= *b) {
+                histogram.buckets[bucket] += 1;
+            }
+            histogram.count += 1;
+            histogram.sum += value;
+        });
+    }
+}
+
+/// Runtime `R` with metered channels, reporting to the global sink installed with
+/// `set_sink`
+#[derive(Clone)]
+pub struct Metered<R>(PhantomData<R>);
+
+impl<R: Runtime> Runtime for Metered<R> {
+    type MessageHandle<P: Send + 'static> = MeteredHandle<R, P>;
+
+    fn spawn<F>(f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        R::spawn(f)
+    }
+
+    type ReceiverStream<P: Send + 'static> = MeteredStream<R::ReceiverStream<P>>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        MeteredStream {
+            inner: R::to_stream::<P>(receiver.inner),
+            channel: receiver.channel,
+        }
+    }
+
+    fn spawn_with(mode: SpawnMode, factory: BloxFactory) -> Result<(), SpawnError> {
+        R::spawn_with(mode, factory)
+    }
+}
+
+impl<R: DynamicRuntime> DynamicRuntime for Metered<R> {}
+
+// Shared by the handles and the receiver of a metered channel
+struct ChannelMetrics {
+    id: String,
+    depth: AtomicUsize,
+    // Held while the depth is read and reported
+    reporting: Mutex<()>,
+    // The global sink if `None`
+    sink: Option<Arc<dyn MetricsSink>>,
+}
+
+impl ChannelMetrics {
+    fn with_sink(&self, f: impl FnOnce(&dyn MetricsSink)) {
+        match &self.sink {
+            Some(sink) => f(sink.as_ref()),
+            None => with_sink(f),
+        }
+    }
+
+    // Reads and reports the depth under a lock, so each report has the depth as it was
+    // after every change reported before it, and the last one reported is current
+    fn report_depth(&self) {
+        let _reporting = self.reporting.lock().unwrap_or_else(|e| e.into_inner());
+        let depth = self.depth.load(Ordering::Relaxed);
+        self.with_sink(|sink| {
+            sink.set_gauge(MAILBOX_DEPTH, &[("channel", &self.id)], depth as f64)
+        });
+    }
+}
+
+/// Handle of `Metered<R>`, wrapping a handle of `R`
+pub struct MeteredHandle<R: Runtime, P: Send + 'static> {
+    inner: R::MessageHandle<P>,
+    channel: Arc<ChannelMetrics>,
+}
+
+impl<R: Runtime, P: Send + 'static> MeteredHandle<R, P> {
+    /// The wrapped handle
+    pub fn inner(&self) -> &R::MessageHandle<P> {
+        &self.inner
+    }
+
+    /// Creates a channel reporting to `sink` instead of the global sink
+    pub fn create_channel_with_sink(
+        id: u16,
+        size: usize,
+        sink: Arc<dyn MetricsSink>,
+    ) -> (Self, <Self as MessageSender>::ReceiverType) {
+        Self::create_channel(id, size, Some(sink))
+    }
+
+    fn create_channel(
+        id: u16,
+        size: usize,
+        sink: Option<Arc<dyn MetricsSink>>,
+    ) -> (Self, <Self as MessageSender>::ReceiverType) {
+        let (inner, receiver) = R::MessageHandle::<P>::create_channel_with_size(id, size);
+        let channel = Arc::new(ChannelMetrics {
+            id: id.to_string(),
+            depth: AtomicUsize::new(0),
+            reporting: Mutex::new(()),
+            sink,
+        });
+        (
+            Self {
+                inner,
+                channel: channel.clone(),
+            },
+            MeteredReceiver {
+                inner: receiver,
+                channel,
+            },
+        )
+    }
+}
+
+impl<R: Runtime, P: Send + 'static> Clone for MeteredHandle<R, P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<R: Runtime, P: Send + 'static> fmt::Debug for MeteredHandle<R, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MeteredHandle({})", self.channel.id)
+    }
+}
+
+impl<R: Runtime, P: Send + 'static> MessageSender for MeteredHandle<R, P> {
+    type PayloadType = P;
+    type SenderType = Self;
+    type ReceiverType = MeteredReceiver<<R::MessageHandle<P> as MessageSender>::ReceiverType>;
+    type ErrorType = <R::MessageHandle<P> as MessageSender>::ErrorType;
+
+    fn try_send(&self, msg: Message<Self::PayloadType>) -> Result<(), Self::ErrorType> {
+        // Counted before sending, so the receiver never sees the depth go below zero
+        self.channel.depth.fetch_add(1, Ordering::Relaxed);
+        match self.inner.try_send(msg) {
+            Ok(()) => {
+                self.channel.report_depth();
+                Ok(())
+            }
+            Err(e) => {
+                self.channel.depth.fetch_sub(1, Ordering::Relaxed);
+                let kind = match R::MessageHandle::<P>::error_kind(&e) {
+                    SendErrorKind::Full => "full",
+                    SendErrorKind::Closed => "closed",
+                    SendErrorKind::Other => "other",
+                };
+                self.channel.with_sink(|sink| {
+                    sink.increment_counter(
+                        SEND_FAILURES_TOTAL,
+                        &[("channel", &self.channel.id), ("kind", kind)],
+                        1,
+                    )
+                });
+                Err(e)
+            }
+        }
+    }
+
+    fn error_kind(error: &Self::ErrorType) -> SendErrorKind {
+        R::MessageHandle::<P>::error_kind(error)
+    }
+
+    fn id(&self) -> u16 {
+        self.inner.id()
+    }
+
+    /// Creates a channel reporting to the global sink, see `set_sink`
+    fn create_channel_with_size(id: u16, size: usize) -> (Self, Self::ReceiverType) {
+        Self::create_channel(id, size, None)
+    }
+}
+
+/// Receiver of a `MeteredHandle` channel
+pub struct MeteredReceiver<T> {
+    inner: T,
+    channel: Arc<ChannelMetrics>,
+}
+
+/// Stream of a `MeteredReceiver`
+pub struct MeteredStream<S> {
+    inner: S,
+    channel: Arc<ChannelMetrics>,
+}
+
+impl<S: Stream + Unpin> Stream for MeteredStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = Pin::new(&mut self.inner).poll_next(cx);
+        if let Poll::Ready(Some(_)) = item {
+            let channel = &self.channel;
+            // Messages sent on the inner handle directly weren't counted
+            let _ = channel
+                .depth
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |d| {
+                    Some(d.saturating_sub(1))
+                });
+            channel.report_depth();
+            channel.with_sink(|sink| {
+                sink.increment_counter(MESSAGES_RECEIVED_TOTAL, &[("channel", &channel.id)], 1)
+            });
+        }
+        item
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::components::Components;
+
+/// Observes what a state machine does, for auditing, metrics and test assertions.
+///
+/// Observers are registered per state machine with `StateMachine::add_observer` and
+/// are called synchronously, in registration order.  All methods default to doing nothing.
+pub trait Observer<C: Components>: Send {
+    /// A message received from a channel is about to be dispatched to `state`, the
+    /// current state.  Called before `on_message`, but not for internal events posted by
+    /// states, which are dispatched again whenever their message is.  Neither is called
+    /// once the state machine has finished
+    fn on_receive(&mut self, _state: &C::States, _message: &C::MessageSet) {}
+
+    /// A message is about to be dispatched to `state`, the current state
+    fn on_message(&mut self, _state: &C::States, _message: &C::MessageSet) {}
+
+    /// The message passed to the last `on_receive` has been handled, along with any
+    /// internal events it posted.  `state` is now the current state
+    fn on_dispatched(&mut self, _state: &C::States) {}
+
+    /// A message reached the root state without being handled.  `state` is the
+    /// current state at the time
+    fn on_unhandled(&mut self, _state: &C::States, _message: &C::MessageSet) {}
+
+    /// `state` has been exited
+    fn on_exit(&mut self, _state: &C::States) {}
+
+    /// `state` has been entered
+    fn on_entry(&mut self, _state: &C::States) {}
+
+    /// A transition from `from` to `to` has completed, all exits and entries have run
+    fn on_transition(&mut self, _from: &C::States, _to: &C::States) {}
+}
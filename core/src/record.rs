@@ -0,0 +1,217 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Record-and-replay of the messages a blox receives.
+//!
+//! A `MessageRecorder` added to a blox's state machine with `add_observer` writes every
+//! message the blox receives from its channels, one JSON object per line, with a
+//! timestamp and the state it was received in.  `replay` feeds a recording into a fresh
+//! state machine, checking it receives each message in the same state as the original.
+//! Replay is deterministic, timestamps are kept for inspection only.
+
+use crate::{components::*, messaging::*, observer::Observer, state_machine::*, std_exports::*};
+use log::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::io::{self, BufRead, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A message set that can be recorded.  Messages that can't be serialized, such as
+/// those carrying channels, return `None` from `to_record` and are left out of
+/// recordings, so a blox that depends on them may not replay faithfully.
+pub trait RecordableMessageSet: MessageSet + Sized {
+    type Record: Serialize + DeserializeOwned;
+
+    fn to_record(&self) -> Option<Self::Record>;
+
+    fn from_record(record: Self::Record) -> Self;
+}
+
+/// One received message in a recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage<R> {
+    /// Microseconds since the Unix epoch when the message was received
+    pub timestamp_micros: u64,
+    /// The state the blox was in when it received the message, formatted with `Debug`
+    pub state: String,
+    pub message: R,
+}
+
+/// The serializable subset of `StandardPayload`, everything but channels and boxed values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StandardRecord {
+    Shutdown,
+    PollHandle,
+    PollState,
+    Error(ErrorString),
+    ChildFinished(u16),
+    RawInbound(u16, RawBytes),
+    RawOutbound(u16, RawBytes),
+}
+
+impl StandardRecord {
+    pub fn from_payload<R: Runtime>(payload: &StandardPayload<R>) -> Option<Self>
+    where
+        <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+    {
+        let record = match payload {
+            StandardPayload::Shutdown => StandardRecord::Shutdown,
+            StandardPayload::PollHandle => StandardRecord::PollHandle,
+            StandardPayload::PollState => StandardRecord::PollState,
+            StandardPayload::Error(e) => StandardRecord::Error(e.clone()),
+            StandardPayload::ChildFinished(id) => StandardRecord::ChildFinished(*id),
+            StandardPayload::RawInbound(from, bytes) => {
+                StandardRecord::RawInbound(*from, bytes.clone())
+            }
+            StandardPayload::RawOutbound(to, bytes) => {
+                StandardRecord::RawOutbound(*to, bytes.clone())
+            }
+            StandardPayload::Handle(_)
+            | StandardPayload::State(_)
+            | StandardPayload::StandardChannel(..) => return None,
+        };
+        Some(record)
+    }
+
+    pub fn into_payload<R: Runtime>(self) -> StandardPayload<R>
+    where
+        <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+    {
+        match self {
+            StandardRecord::Shutdown => StandardPayload::Shutdown,
+            StandardRecord::PollHandle => StandardPayload::PollHandle,
+            StandardRecord::PollState => StandardPayload::PollState,
+            StandardRecord::Error(e) => StandardPayload::Error(e),
+            StandardRecord::ChildFinished(id) => StandardPayload::ChildFinished(id),
+            StandardRecord::RawInbound(from, bytes) => StandardPayload::RawInbound(from, bytes),
+            StandardRecord::RawOutbound(to, bytes) => StandardPayload::RawOutbound(to, bytes),
+        }
+    }
+}
+
+/// Observer writing every recordable message its state machine receives to `writer`.
+/// Each record is flushed as it is written, so a recording survives a crash.
+pub struct MessageRecorder<W> {
+    writer: W,
+}
+
+impl<W: Write + Send> MessageRecorder<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    fn write<R: Serialize>(&mut self, recorded: &RecordedMessage<R>) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, recorded)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+}
+
+impl<C, W> Observer<C> for MessageRecorder<W>
+where
+    C: Components,
+    C::MessageSet: RecordableMessageSet,
+    W: Write + Send,
+{
+    fn on_receive(&mut self, state: &C::States, message: &C::MessageSet) {
+        let Some(record) = message.to_record() else {
+            trace!("Not recording {:?}", message);
+            return;
+        };
+        let timestamp_micros = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_micros() as u64);
+        let recorded = RecordedMessage {
+            timestamp_micros,
+            state: format!("{:?}", state),
+            message: record,
+        };
+        if let Err(e) = self.write(&recorded) {
+            error!("Failed to record message: {}", e);
+        }
+    }
+}
+
+/// Error reading or replaying a recording
+#[derive(Debug)]
+pub enum RecordError {
+    Io(io::Error),
+    /// Line `line`, counted from 1, isn't a recorded message
+    Parse {
+        line: usize,
+        error: serde_json::Error,
+    },
+    /// The message at `index` was received in `actual` on replay, not `recorded`
+    Diverged {
+        index: usize,
+        recorded: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordError::Io(e) => write!(f, "Failed to read recording: {}", e),
+            RecordError::Parse { line, error } => {
+                write!(f, "Invalid record on line {}: {}", line, error)
+            }
+            RecordError::Diverged {
+                index,
+                recorded,
+                actual,
+            } => write!(
+                f,
+                "Replay diverged at message {}: recorded in {}, replayed in {}",
+                index, recorded, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+/// Reads a recording written by `MessageRecorder`
+pub fn read_recording<R: DeserializeOwned>(
+    reader: impl BufRead,
+) -> Result<Vec<RecordedMessage<R>>, RecordError> {
+    let mut recording = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(RecordError::Io)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let recorded = serde_json::from_str(&line)
+            .map_err(|error| RecordError::Parse { line: i + 1, error })?;
+        recording.push(recorded);
+    }
+    Ok(recording)
+}
+
+/// Dispatches every message of `recording` to `state_machine`, which must have been
+/// initialized like the recorded blox.  Stops at the first message that arrives in a
+/// different state than it was recorded in.  Returns the number of messages replayed.
+pub fn replay<C>(
+    state_machine: &mut StateMachine<C>,
+    recording: impl IntoIterator<
+        Item = RecordedMessage<<C::MessageSet as RecordableMessageSet>::Record>,
+    >,
+) -> Result<usize, RecordError>
+where
+    C: Components,
+    C::States: State<C> + Clone + PartialEq + Default,
+    C::MessageSet: RecordableMessageSet,
+{
+    let mut replayed = 0;
+    for (index, recorded) in recording.into_iter().enumerate() {
+        let actual = format!("{:?}", state_machine.current_state);
+        if actual != recorded.state {
+            return Err(RecordError::Diverged {
+                index,
+                recorded: recorded.state,
+                actual,
+            });
+        }
+        state_machine.dispatch(C::MessageSet::from_record(recorded.message));
+        replayed += 1;
+    }
+    Ok(replayed)
+}
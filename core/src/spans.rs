@@ -0,0 +1,62 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Tracing spans for bloxes, with the `tracing` feature.
+//!
+//! A blox's run loop runs in a `blox` span carrying its name and id.  Each dispatch is a
+//! `dispatch` span inside it with the current state and the message's variant name,
+//! the full message is only in a trace level `message` event in that span.  Each
+//! transition is a `transition` event with the states it went from and to.  Without the
+//! feature nothing is traced.
+
+use crate::std_exports::*;
+
+/// Runs `future`, the run loop of the blox `name` with id `id`, in its `blox` span
+#[cfg(feature = "tracing")]
+pub fn in_blox_span<F: Future>(
+    name: &'static str,
+    id: u16,
+    future: F,
+) -> tracing::instrument::Instrumented<F> {
+    let span = tracing::info_span!("blox", blox.name = name, blox.id = id);
+    tracing::Instrument::instrument(future, span)
+}
+
+/// Runs `future` as is, without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+pub fn in_blox_span<F: Future>(_name: &'static str, _id: u16, future: F) -> F {
+    future
+}
+
+/// Displays the variant name of an enum, the start of its `Debug` output, without
+/// formatting the fields or allocating
+#[cfg(feature = "tracing")]
+pub(crate) struct VariantName<'a, T: fmt::Debug>(pub &'a T);
+
+#[cfg(feature = "tracing")]
+impl<T: fmt::Debug> fmt::Display for VariantName<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use core::fmt::Write;
+        write!(UntilField { f, done: false }, "{:?}", self.0)
+    }
+}
+
+// Passes output through up to the first character that can't be in an identifier
+#[cfg(feature = "tracing")]
+struct UntilField<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    done: bool,
+}
+
+#[cfg(feature = "tracing")]
+impl fmt::Write for UntilField<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.done {
+            return Ok(());
+        }
+        let end = s
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(s.len());
+        self.done = end < s.len();
+        self.f.write_str(&s[..end])
+    }
+}
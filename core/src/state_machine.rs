@@ -1,12 +1,34 @@
 // Copyright 2025 Bloxide, all rights reserved
 
-use crate::{components::*, std_exports::*};
+#[cfg(feature = "alloc")]
+use crate::{activity::Activity, messaging::MessageSet};
+use crate::{components::*, observer::Observer, std_exports::*, unhandled::*};
 use log::*;
 
 pub trait StateEnum: Default + fmt::Debug {
     fn new() -> Self {
         Self::default()
     }
+
+    /// Every state of the blox, used to validate the state hierarchy
+    fn all_states() -> &'static [Self]
+    where
+        Self: Sized;
+
+    /// Transitions the blox can take, `(from, to)`, for diagrams.  Nothing checks them
+    /// against what `handle_message` does, none are declared by default.
+    fn transitions() -> &'static [(Self, Self)]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
+
+    /// The state entered when a state hook returns an error.
+    /// `None` leaves the state machine where it is after logging the error.
+    fn error_state() -> Option<Self> {
+        None
+    }
 }
 
 pub trait ExtendedState {
@@ -16,16 +38,199 @@ pub trait ExtendedState {
 
 /// Used as `Option<Transition<T>>`, `None` = No transition
 /// Errors handled as transitions to a Error state
+///
+/// `To` exits the current state and its ancestors up to, not including, the lowest
+/// common ancestor of the current and target states, innermost first, then enters the
+/// target's ancestors below it and the target, outermost first.  Transitions are local:
+/// a transition to the current state runs no exits or entries, and a transition to an
+/// ancestor only exits the states below it.
 pub enum Transition<T, M> {
     To(T),
     Parent(M),
 }
 
+/// Error returned by the fallible state hooks
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateError {
+    /// Sending a message to another blox failed, holds the rendered send error
+    Send(ErrorString),
+    /// A handle the state depends on has not been set
+    MissingHandle(&'static str),
+    /// A message reached the root state and the unhandled policy is `Error`
+    Unhandled(ErrorString),
+    /// Any other error raised by a state
+    Other(ErrorString),
+}
+
+impl StateError {
+    pub fn send<E: fmt::Debug>(error: E) -> Self {
+        StateError::Send(error_string(format_args!("{:?}", error)))
+    }
+
+    pub fn other<D: fmt::Display>(error: D) -> Self {
+        StateError::Other(error_string(format_args!("{}", error)))
+    }
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::Send(e) => write!(f, "Failed to send message: {}", e),
+            StateError::MissingHandle(name) => write!(f, "Missing handle: {}", name),
+            StateError::Unhandled(message) => write!(f, "Unhandled message: {}", message),
+            StateError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// A problem found in a blox's state hierarchy by `StateMachine::validate`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError<S> {
+    /// `StateEnum::all_states` returned no states
+    NoStates,
+    /// No state is its own parent
+    NoRoot,
+    /// More than one state is its own parent
+    MultipleRoots(StateVec<S>),
+    /// A state's parent is not listed in `StateEnum::all_states`
+    UnknownParent { state: S, parent: S },
+    /// Following `parent` from the first state leads back to it
+    Cycle(StateVec<S>),
+    /// States whose parent chain never reaches the root
+    Unreachable(StateVec<S>),
+    /// A state the blox uses (its default, error or initial state, or a declared
+    /// transition's) is not listed in `StateEnum::all_states`
+    Undeclared(S),
+}
+
+impl<S: fmt::Debug> fmt::Display for ValidationError<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::NoStates => write!(f, "No states declared"),
+            ValidationError::NoRoot => write!(f, "No root state, the root is its own parent"),
+            ValidationError::MultipleRoots(roots) => write!(f, "Multiple root states: {:?}", roots),
+            ValidationError::UnknownParent { state, parent } => {
+                write!(f, "Parent {:?} of {:?} is not a known state", parent, state)
+            }
+            ValidationError::Cycle(cycle) => write!(f, "Cycle in parent chain: {:?}", cycle),
+            ValidationError::Unreachable(states) => {
+                write!(f, "States not reachable from the root: {:?}", states)
+            }
+            ValidationError::Undeclared(state) => {
+                write!(f, "State {:?} is not listed in all_states", state)
+            }
+        }
+    }
+}
+
+/// Result of `State::try_handle_message`
+pub type HandlerResult<T, M> = Result<Option<Transition<T, M>>, StateError>;
+
+// The state hierarchy of a blox, computed once when the state machine is created so
+// transitions can walk it by index without allocating
+struct StateTable<S: 'static> {
+    states: &'static [S],
+    parents: StateVec<usize>,
+    depths: StateVec<usize>,
+}
+
+impl<S: PartialEq + fmt::Debug> StateTable<S> {
+    fn new<C>() -> Self
+    where
+        C: Components<States = S>,
+        S: State<C> + StateEnum,
+    {
+        let states = S::all_states();
+        #[cfg(not(feature = "alloc"))]
+        assert!(
+            states.len() <= MAX_STATES,
+            "{} states declared, at most {} are supported without alloc",
+            states.len(),
+            MAX_STATES
+        );
+        // Unknown parents are treated as roots here, `validate` reports them
+        let parents: StateVec<usize> = states
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let parent = s.parent();
+                states.iter().position(|p| *p == parent).unwrap_or(i)
+            })
+            .collect();
+        // Depth walks are bounded by the number of states so a cycle can't spin forever
+        let depths = (0..states.len())
+            .map(|i| {
+                let mut depth = 0;
+                let mut current = i;
+                while parents[current] != current && depth < states.len() {
+                    current = parents[current];
+                    depth += 1;
+                }
+                depth
+            })
+            .collect();
+        Self {
+            states,
+            parents,
+            depths,
+        }
+    }
+
+    fn index_of(&self, state: &S) -> usize {
+        self.states
+            .iter()
+            .position(|s| s == state)
+            .unwrap_or_else(|| panic!("State {:?} is not listed in all_states", state))
+    }
+
+    fn is_root(&self, index: usize) -> bool {
+        self.parents[index] == index
+    }
+
+    fn ancestor_at_depth(&self, mut index: usize, depth: usize) -> usize {
+        while self.depths[index] > depth {
+            index = self.parents[index];
+        }
+        index
+    }
+
+    fn lca(&self, a: usize, b: usize) -> usize {
+        let depth = self.depths[a].min(self.depths[b]);
+        let mut a = self.ancestor_at_depth(a, depth);
+        let mut b = self.ancestor_at_depth(b, depth);
+        while a != b {
+            a = self.parents[a];
+            b = self.parents[b];
+        }
+        a
+    }
+}
+
 pub struct StateMachine<C: Components> {
     pub current_state: C::States,
     // ExtendedState stored here to be passed to each state
     pub extended_state: C::ExtendedState,
     pub self_handles: C::Handles,
+    // Index of current_state in the state table
+    current: usize,
+    // Taken out while a message is being handled so states can be borrowed from it
+    table: Option<StateTable<C::States>>,
+    // Internal events posted by states, processed before the next external message
+    posted: EventQueue<C::MessageSet>,
+    // Set once a top-level final state has been reached
+    finished: bool,
+    // The most recent error returned by a state hook
+    last_error: Option<StateError>,
+    // Do-activities started by active states
+    #[cfg(feature = "alloc")]
+    activities: Vec<Activity>,
+    // The state that owns activities started right now, the state being entered during entry
+    #[cfg(feature = "alloc")]
+    activity_owner: usize,
+    // Observers are boxed, so without alloc there are none
+    #[cfg(feature = "alloc")]
+    observers: Vec<Box<dyn Observer<C>>>,
+    unhandled_policy: UnhandledPolicy,
 }
 
 impl<C> StateMachine<C>
@@ -34,33 +239,341 @@ where
     C::States: State<C> + Clone + PartialEq + Default,
 {
     pub fn new(extended_state: C::ExtendedState, self_handles: C::Handles) -> Self {
+        let table = StateTable::new::<C>();
+        let current_state = C::States::default();
+        let current = table.states.iter().position(|s| *s == current_state);
         Self {
-            current_state: C::States::default(),
+            current_state,
             extended_state,
             self_handles,
+            current: current.unwrap_or_default(),
+            table: Some(table),
+            posted: EventQueue::new(),
+            finished: false,
+            last_error: None,
+            #[cfg(feature = "alloc")]
+            activities: Vec::new(),
+            #[cfg(feature = "alloc")]
+            activity_owner: current.unwrap_or_default(),
+            #[cfg(feature = "alloc")]
+            observers: Vec::new(),
+            unhandled_policy: UnhandledPolicy::default(),
         }
     }
 
+    /// Sets what happens to messages no state handles, `UnhandledPolicy::Log` by default
+    pub fn set_unhandled_policy(&mut self, policy: UnhandledPolicy) {
+        self.unhandled_policy = policy;
+    }
+
+    /// Registers an observer, called for every message, exit, entry and transition
+    #[cfg(feature = "alloc")]
+    pub fn add_observer<O: Observer<C> + 'static>(&mut self, observer: O) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// The most recent error returned by a state hook, if any
+    pub fn last_error(&self) -> Option<&StateError> {
+        self.last_error.as_ref()
+    }
+
+    /// Returns true once the state machine has reached a top-level final state.
+    /// A finished state machine no longer handles messages.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
     // Initializes the state machine, performs the Uninit state transition
+    // Panics if the state hierarchy is invalid, see `validate`, or if `uninit` or
+    // `entry_point` is not listed in `StateEnum::all_states`
     pub fn init(&mut self, uninit: &C::States, entry_point: &C::States) {
-        uninit.on_exit(self);
-        self.change_state(entry_point.clone());
+        let mut errors = self.validate().err().unwrap_or_default();
+        for state in [uninit, entry_point] {
+            let undeclared = ValidationError::Undeclared(state.clone());
+            if !C::States::all_states().contains(state) && !errors.contains(&undeclared) {
+                let _ = errors.try_push(undeclared);
+            }
+        }
+        if !errors.is_empty() {
+            for e in errors.iter() {
+                error!("Invalid state hierarchy: {}", e);
+            }
+            panic!("Invalid state hierarchy: {:?}", errors);
+        }
+        let table = self.take_table();
+        if let Err(e) = uninit.try_on_exit(self) {
+            // The entry point is never entered, the blox stays in its error state
+            self.fail(&table, e);
+            self.process_posted(&table);
+            self.table = Some(table);
+            return;
+        }
+        self.change_state(&table, table.index_of(entry_point));
+        self.process_posted(&table);
+        self.table = Some(table);
     }
 
-    // This is how messages get handled.  Structured so it can be called recursively for Parent message handling
-    pub fn dispatch(&mut self, message: C::MessageSet, state: &C::States)
+    /// Checks the state hierarchy declared by `StateEnum::all_states` and `State::parent`.
+    ///
+    /// There must be exactly one root (a state that is its own parent), every parent must
+    /// be a declared state, and every state's parent chain must reach the root without
+    /// cycles.  The default state, the error state and the states of declared transitions
+    /// must be declared too.  All problems found are returned.
+    /// Without alloc at most `MAX_STATES` errors are returned.
+    pub fn validate(&self) -> Result<(), StateVec<ValidationError<C::States>>> {
+        let states = C::States::all_states();
+        let mut errors = StateVec::new();
+        if states.is_empty() {
+            let _ = errors.try_push(ValidationError::NoStates);
+            return Err(errors);
+        }
+
+        let roots: StateVec<C::States> = states
+            .iter()
+            .filter(|s| s.parent() == **s)
+            .cloned()
+            .collect();
+        match roots.len() {
+            0 => {
+                let _ = errors.try_push(ValidationError::NoRoot);
+            }
+            1 => {}
+            _ => {
+                let _ = errors.try_push(ValidationError::MultipleRoots(roots.clone()));
+            }
+        }
+
+        for state in states.iter() {
+            let parent = state.parent();
+            if !states.contains(&parent) {
+                let _ = errors.try_push(ValidationError::UnknownParent {
+                    state: state.clone(),
+                    parent,
+                });
+            }
+        }
+
+        // Walk each parent chain, a chain as long as the number of states must loop
+        let mut unreachable = StateVec::new();
+        let mut in_cycle: StateVec<C::States> = StateVec::new();
+        for state in states.iter() {
+            let mut chain = StateVec::new();
+            let _ = chain.try_push(state.clone());
+            let mut current = state.clone();
+            let mut reached_root = false;
+            loop {
+                let parent = current.parent();
+                if parent == current {
+                    reached_root = roots.len() == 1 && roots[0] == current;
+                    break;
+                }
+                if let Some(start) = chain.iter().position(|s| *s == parent) {
+                    // Report each cycle once, from the first of its states
+                    if start == 0 && !in_cycle.contains(state) {
+                        for s in chain.iter() {
+                            let _ = in_cycle.try_push(s.clone());
+                        }
+                        let _ = errors.try_push(ValidationError::Cycle(chain));
+                    }
+                    break;
+                }
+                // Unknown parents can lead anywhere, stop once every state could be in the chain
+                if chain.len() >= states.len() || chain.try_push(parent.clone()).is_err() {
+                    break;
+                }
+                current = parent;
+            }
+            if !reached_root && roots.len() == 1 {
+                let _ = unreachable.try_push(state.clone());
+            }
+        }
+        if !unreachable.is_empty() {
+            let _ = errors.try_push(ValidationError::Unreachable(unreachable));
+        }
+
+        // Transitions to these would panic looking them up
+        let used = [Some(C::States::default()), C::States::error_state()];
+        let transitions = C::States::transitions()
+            .iter()
+            .flat_map(|(from, to)| [from, to])
+            .cloned();
+        for state in used.into_iter().flatten().chain(transitions) {
+            if states.contains(&state) {
+                continue;
+            }
+            let undeclared = ValidationError::Undeclared(state);
+            if !errors.contains(&undeclared) {
+                let _ = errors.try_push(undeclared);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Starts a do-activity tied to the lifetime of a state.
+    ///
+    /// The activity is spawned on `R` and, when it completes, its output is sent through
+    /// `handle` (normally one of the blox's own handles) so it arrives as a message in
+    /// the blox's message set.  Called from `on_entry` the activity belongs to the state
+    /// being entered, from `on_exit` to the parent of the state exiting, otherwise to the
+    /// current state.  It is aborted when that state exits.
+    #[cfg(feature = "alloc")]
+    pub fn start_activity<R, P, F>(&mut self, handle: &R::MessageHandle<P>, activity: F)
     where
-        C::States: State<C>,
+        R: DynamicRuntime,
+        P: Send + 'static,
+        F: Future<Output = P> + Send + 'static,
     {
-        let transition = state.handle_message(self, message);
+        trace!("Starting activity for {:?}", self.current_state);
+        self.prune_activities();
+        self.activities.push(Activity::spawn::<R, P, F>(
+            self.activity_owner,
+            handle,
+            activity,
+        ));
+    }
+
+    /// Number of activities started by active states that haven't finished yet
+    #[cfg(feature = "alloc")]
+    pub fn activity_count(&self) -> usize {
+        self.activities.iter().filter(|a| !a.is_finished()).count()
+    }
+
+    // Drops the activities that have delivered their result
+    #[cfg(feature = "alloc")]
+    fn prune_activities(&mut self) {
+        self.activities.retain(|activity| !activity.is_finished());
+    }
+
+    // Aborts the activities started by a state, called when it exits
+    #[cfg(feature = "alloc")]
+    fn abort_activities(&mut self, owner: usize) {
+        self.activities.retain(|activity| {
+            if activity.owner == owner {
+                activity.abort();
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Posts an internal event to this state machine.
+    ///
+    /// Posted events are queued and dispatched to the current state once the
+    /// current message (including any transition it triggers) has been fully
+    /// handled, and before the next external message is taken from a channel.
+    /// Can be called from `on_entry`, `on_exit` and `handle_message`.
+    /// Without alloc at most `MAX_POSTED_EVENTS` can be queued, further events are dropped.
+    pub fn post(&mut self, event: C::MessageSet) {
+        trace!("Posting internal event");
+        if let Err(event) = self.posted.try_push(event) {
+            error!("Internal event queue full, dropping {:?}", event);
+        }
+    }
+
+    // This is how messages get handled.  Runs the message to completion, including any
+    // internal events posted while handling it.  Called from within a state it behaves like
+    // `post`.  Once finished, messages are dropped without reaching the observers
+    pub fn dispatch(&mut self, message: C::MessageSet) {
+        if self.finished {
+            trace!("State machine finished, dropping message");
+            return;
+        }
+        let Some(table) = self.table.take() else {
+            self.post(message);
+            return;
+        };
+        let current = &table.states[self.current];
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            "dispatch",
+            state = ?current,
+            message = %crate::spans::VariantName(&message)
+        )
+        .entered();
+        #[cfg(feature = "tracing")]
+        tracing::trace!(message = ?message);
+        self.observe(|observer| observer.on_receive(current, &message));
+        // Results of finished activities arrive as messages, their entries aren't needed
+        #[cfg(feature = "alloc")]
+        self.prune_activities();
+        self.notify_message(&table, &message);
+        self.dispatch_to(&table, message, self.current);
+        self.process_posted(&table);
+        let current = &table.states[self.current];
+        self.observe(|observer| observer.on_dispatched(current));
+        self.table = Some(table);
+    }
+
+    fn take_table(&mut self) -> StateTable<C::States> {
+        self.table
+            .take()
+            .expect("State machine is already handling a message")
+    }
+
+    // Drains the internal event queue, dispatching each event to the state current at that time
+    fn process_posted(&mut self, table: &StateTable<C::States>) {
+        while let Some(event) = self.posted.pop_front() {
+            trace!("Dispatching posted event");
+            self.notify_message(table, &event);
+            self.dispatch_to(table, event, self.current);
+        }
+    }
+
+    fn notify_message(&mut self, table: &StateTable<C::States>, message: &C::MessageSet) {
+        if self.finished {
+            return;
+        }
+        let current = &table.states[self.current];
+        self.observe(|observer| observer.on_message(current, message));
+    }
+
+    // Calls `f` with every observer
+    #[cfg_attr(not(feature = "alloc"), allow(unused_mut, unused_variables))]
+    fn observe(&mut self, mut f: impl FnMut(&mut dyn Observer<C>)) {
+        #[cfg(feature = "alloc")]
+        for observer in self.observers.iter_mut() {
+            f(observer.as_mut());
+        }
+    }
+
+    // Structured so it can be called recursively for Parent message handling
+    fn dispatch_to(&mut self, table: &StateTable<C::States>, message: C::MessageSet, state: usize) {
+        if self.finished {
+            trace!("State machine finished, dropping message");
+            return;
+        }
+        // The root state never handles messages, anything reaching it is unhandled
+        if table.is_root(state) {
+            trace!(
+                "Unhandled message in state {:?}",
+                table.states[self.current]
+            );
+            let current = &table.states[self.current];
+            self.observe(|observer| observer.on_unhandled(current, &message));
+            self.unhandled(table, message);
+            return;
+        }
+        let transition = match table.states[state].try_handle_message(self, message) {
+            Ok(transition) => transition,
+            Err(e) => {
+                self.fail(table, e);
+                return;
+            }
+        };
         match transition {
             Some(Transition::Parent(message)) => {
                 trace!("Transitioning to parent state");
-                self.dispatch(message, &state.parent());
+                self.dispatch_to(table, message, table.parents[state]);
             }
             Some(Transition::To(new_state)) => {
                 trace!("Transitioning to state: {:?}", new_state);
-                self.change_state(new_state);
+                self.change_state(table, table.index_of(&new_state));
             }
             _ => {
                 // Do nothing if transition is None regardless of message
@@ -68,57 +581,162 @@ where
         }
     }
 
-    fn build_state_path(&self, start_state: C::States) -> Vec<C::States> {
-        let mut path = Vec::new();
-        let mut current = Some(start_state);
-        while let Some(s) = current {
-            path.push(s.clone());
-            current = match s.parent() {
-                p if p == s => None, // Check if parent equals self (for Uninit state)
-                p => Some(p),
-            };
-        }
-        path.reverse();
-        path
-    }
-
-    fn find_lca_index(&self, current_path: &[C::States], dest_path: &[C::States]) -> usize {
-        let mut lca_index = 0;
-        while lca_index < current_path.len().min(dest_path.len())
-            && current_path[lca_index] == dest_path[lca_index]
-        {
-            lca_index += 1;
+    // Applies the unhandled policy to a message that reached the root state
+    fn unhandled(&mut self, table: &StateTable<C::States>, message: C::MessageSet) {
+        match &self.unhandled_policy {
+            UnhandledPolicy::Ignore => {}
+            UnhandledPolicy::Log => {
+                warn!(
+                    "Unhandled message in state {:?}: {:?}",
+                    table.states[self.current], message
+                );
+            }
+            #[cfg(feature = "alloc")]
+            UnhandledPolicy::DeadLetter(sink) => {
+                let mut state_path = Vec::new();
+                let mut state = self.current;
+                loop {
+                    state_path.push(format!("{:?}", table.states[state]));
+                    if table.is_root(state) {
+                        break;
+                    }
+                    state = table.parents[state];
+                }
+                state_path.reverse();
+                sink.send_dead_letter(DeadLetter {
+                    source_id: message.source_id(),
+                    state_path,
+                    message: format!("{:?}", message),
+                });
+            }
+            UnhandledPolicy::Error => {
+                let message = error_string(format_args!("{:?}", message));
+                self.fail(table, StateError::Unhandled(message));
+            }
         }
-        lca_index
     }
 
     // This is how states get changed
     // Traverses the on_exit and on_entry functions
-    fn change_state(&mut self, new_state: C::States) {
-        // Build current state path
-        let current_path = self.build_state_path(self.current_state.clone());
-        trace!("Current state path: {:?}", current_path);
-
-        // Build destination state path
-        let dest_path = self.build_state_path(new_state.clone());
-        trace!("Destination state path: {:?}", dest_path);
+    fn change_state(&mut self, table: &StateTable<C::States>, new_state: usize) {
+        let lca = table.lca(self.current, new_state);
+        trace!(
+            "Changing state from {:?} to {:?}, LCA: {:?}",
+            table.states[self.current],
+            table.states[new_state],
+            table.states[lca]
+        );
 
-        // Find LCA
-        let lca_index = self.find_lca_index(&current_path, &dest_path);
-        trace!("LCA index: {:?}", lca_index);
+        // A failing hook doesn't stop the transition, the error is routed once it is complete
+        let mut failure = None;
 
         // Exit from current state up to (but not including) LCA
-        for state in current_path[lca_index..].iter().rev() {
-            state.on_exit(self);
+        let mut state = self.current;
+        while state != lca {
+            // Activities started on exit outlive the state exiting
+            #[cfg(feature = "alloc")]
+            {
+                self.activity_owner = table.parents[state];
+            }
+            if let Err(e) = table.states[state].try_on_exit(self) {
+                failure.get_or_insert(e);
+            }
+            #[cfg(feature = "alloc")]
+            self.abort_activities(state);
+            self.observe(|observer| observer.on_exit(&table.states[state]));
+            state = table.parents[state];
         }
 
         // Enter from LCA down to destination
-        for state in dest_path[lca_index..].iter() {
-            state.on_entry(self);
+        for depth in table.depths[lca] + 1..=table.depths[new_state] {
+            let state = table.ancestor_at_depth(new_state, depth);
+            #[cfg(feature = "alloc")]
+            {
+                self.activity_owner = state;
+            }
+            if let Err(e) = table.states[state].try_on_entry(self) {
+                failure.get_or_insert(e);
+            }
+            self.observe(|observer| observer.on_entry(&table.states[state]));
+        }
+        #[cfg(feature = "alloc")]
+        {
+            self.activity_owner = new_state;
         }
 
+        let previous = self.current;
+
         // Set new current state
-        self.current_state = new_state;
+        if self.current != new_state {
+            self.current = new_state;
+            self.current_state = table.states[new_state].clone();
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            from = ?table.states[previous],
+            to = ?table.states[new_state],
+            "transition"
+        );
+        self.observe(|observer| {
+            observer.on_transition(&table.states[previous], &table.states[new_state])
+        });
+
+        if let Some(e) = failure {
+            self.fail(table, e);
+            return;
+        }
+
+        if table.states[new_state].is_final() {
+            self.complete(table, new_state);
+        }
+    }
+
+    // Emits the completion event for a final state to its enclosing composite state.
+    // A final state directly below the top state finishes the whole state machine
+    fn complete(&mut self, table: &StateTable<C::States>, final_state: usize) {
+        let composite = table.parents[final_state];
+        if table.is_root(composite) {
+            trace!(
+                "Top-level final state reached: {:?}",
+                table.states[final_state]
+            );
+            self.finished = true;
+            return;
+        }
+
+        trace!(
+            "Completion event for {:?} from {:?}",
+            table.states[composite],
+            table.states[final_state]
+        );
+        if let Some(new_state) =
+            table.states[composite].on_completion(self, &table.states[final_state])
+        {
+            trace!("Completion transition to state: {:?}", new_state);
+            self.change_state(table, table.index_of(&new_state));
+        }
+    }
+
+    // Stores the error and transitions to the blox's error state, unless already there
+    fn fail(&mut self, table: &StateTable<C::States>, error: StateError) {
+        error!("State {:?} failed: {}", self.current_state, error);
+        self.last_error = Some(error);
+        if let Some(error_state) = C::States::error_state() {
+            let error_state = table.index_of(&error_state);
+            if error_state != self.current {
+                self.change_state(table, error_state);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C: Components> Drop for StateMachine<C> {
+    // Activities don't outlive the state machine that started them
+    fn drop(&mut self) {
+        for activity in self.activities.iter() {
+            activity.abort();
+        }
     }
 }
 
@@ -132,14 +750,54 @@ pub trait State<C: Components>: fmt::Debug + 'static {
         trace!("State on_exit: {:?}", self);
     }
 
-    // All states must have a parent state
-    fn parent(&self) -> C::States {
-        panic!("No parent for this state");
+    /// The state's parent, the root state is its own parent
+    fn parent(&self) -> C::States;
+
+    /// Final states complete the region of their parent state.  Entering a final state
+    /// delivers a completion event to the parent via `on_completion`, or finishes the
+    /// state machine if the parent is the top state.
+    fn is_final(&self) -> bool {
+        false
+    }
+
+    /// Handles the completion event raised when a child state reaches a final state.
+    /// Returning a state performs a completion transition, `None` stays in the final state.
+    fn on_completion(
+        &self,
+        _state_machine: &mut StateMachine<C>,
+        _final_state: &C::States,
+    ) -> Option<C::States> {
+        None
     }
 
+    // States implement either handle_message or try_handle_message.  A state that
+    // implements neither forwards every message to its parent, so they end up unhandled.
+    // The root state is never asked, messages forwarded to it are unhandled
     fn handle_message(
+        &self,
+        _state_machine: &mut StateMachine<C>,
+        message: C::MessageSet,
+    ) -> Option<Transition<C::States, C::MessageSet>> {
+        Some(Transition::Parent(message))
+    }
+
+    // Fallible variants of the hooks, these are what the state machine calls.
+    // Returning an error transitions the state machine to `StateEnum::error_state`
+    fn try_on_entry(&self, state_machine: &mut StateMachine<C>) -> Result<(), StateError> {
+        self.on_entry(state_machine);
+        Ok(())
+    }
+
+    fn try_on_exit(&self, state_machine: &mut StateMachine<C>) -> Result<(), StateError> {
+        self.on_exit(state_machine);
+        Ok(())
+    }
+
+    fn try_handle_message(
         &self,
         state_machine: &mut StateMachine<C>,
         message: C::MessageSet,
-    ) -> Option<Transition<C::States, C::MessageSet>>;
+    ) -> HandlerResult<C::States, C::MessageSet> {
+        Ok(self.handle_message(state_machine, message))
+    }
 }
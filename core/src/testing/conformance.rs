@@ -0,0 +1,230 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Conformance suite for `Runtime` implementations.
+//!
+//! Each check is an async function generic over the runtime, panicking when the
+//! runtime doesn't behave as bloxes expect.  `conformance_tests!` turns them into
+//! `#[test]`s, given a function that blocks on a future within the runtime.
+
+use crate::blox::supervisor::{components::*, ext_state::*, messaging::*};
+use crate::{components::*, messaging::*, state_machine::*, std_exports::*};
+use futures_util::StreamExt;
+
+/// Messages sent on one handle arrive in the order they were sent
+pub async fn channel_preserves_order<R: Runtime>() {
+    let (handle, rx) = R::MessageHandle::<u32>::create_channel_with_size(1, 8);
+    for i in 0..8 {
+        handle.try_send(Message::new(1, i)).unwrap();
+    }
+    let mut stream = R::to_stream(rx);
+    for i in 0..8 {
+        let msg = stream.next().await.expect("stream ended early");
+        assert_eq!(msg.payload, i);
+        assert_eq!(msg.source_id(), 1);
+    }
+}
+
+/// `try_send` on a channel at capacity fails with `SendErrorKind::Full`
+pub async fn try_send_reports_full<R: Runtime>() {
+    let (handle, rx) = R::MessageHandle::<u32>::create_channel_with_size(1, 2);
+    handle.try_send(Message::new(1, 0)).unwrap();
+    handle.try_send(Message::new(1, 1)).unwrap();
+    let error = handle
+        .try_send(Message::new(1, 2))
+        .expect_err("send to a full channel succeeded");
+    assert_eq!(
+        R::MessageHandle::<u32>::error_kind(&error),
+        SendErrorKind::Full
+    );
+
+    // Receiving one message makes room for another
+    let mut stream = R::to_stream(rx);
+    assert_eq!(stream.next().await.map(|msg| msg.payload), Some(0));
+    handle.try_send(Message::new(1, 2)).unwrap();
+}
+
+/// `try_send` after the receiver is dropped fails with `SendErrorKind::Closed`
+pub async fn try_send_reports_closed<R: Runtime>() {
+    let (handle, rx) = R::MessageHandle::<u32>::create_channel_with_size(1, 2);
+    drop(rx);
+    let error = handle
+        .try_send(Message::new(1, 0))
+        .expect_err("send to a closed channel succeeded");
+    assert_eq!(
+        R::MessageHandle::<u32>::error_kind(&error),
+        SendErrorKind::Closed
+    );
+}
+
+/// A stream ends once every handle, clones included, has been dropped, after
+/// yielding the messages still queued
+pub async fn stream_ends_when_senders_drop<R: Runtime>() {
+    let (handle, rx) = R::MessageHandle::<u32>::create_channel_with_size(1, 4);
+    let clone = handle.clone();
+    handle.try_send(Message::new(1, 0)).unwrap();
+    clone.try_send(Message::new(1, 1)).unwrap();
+    drop(handle);
+    let mut stream = R::to_stream(rx);
+    assert_eq!(stream.next().await.map(|msg| msg.payload), Some(0));
+    drop(clone);
+    assert_eq!(stream.next().await.map(|msg| msg.payload), Some(1));
+    assert!(stream.next().await.is_none());
+}
+
+/// A spawned future runs concurrently with its spawner
+pub async fn spawned_task_runs<R: Runtime>()
+where
+    <R::MessageHandle<u32> as MessageSender>::ReceiverType: Send,
+{
+    let (request, request_rx) = R::MessageHandle::<u32>::create_channel_with_size(1, 4);
+    let (reply, reply_rx) = R::MessageHandle::<u32>::create_channel_with_size(2, 4);
+    R::spawn(async move {
+        let mut requests = R::to_stream(request_rx);
+        while let Some(msg) = requests.next().await {
+            reply.try_send(Message::new(2, msg.payload * 2)).unwrap();
+        }
+    });
+
+    let mut replies = R::to_stream(reply_rx);
+    for i in 1..4 {
+        request.try_send(Message::new(1, i)).unwrap();
+        assert_eq!(replies.next().await.map(|msg| msg.payload), Some(i * 2));
+    }
+    // The task ends, dropping its reply handle, once its requests stream ends
+    drop(request);
+    assert!(replies.next().await.is_none());
+}
+
+/// `spawn_with` either runs the factory's future or reports the mode unsupported
+pub async fn spawn_with_runs_or_is_unsupported<R: Runtime>() {
+    for mode in [SpawnMode::Local, SpawnMode::Dedicated] {
+        let (reply, reply_rx) = R::MessageHandle::<u32>::create_channel_with_size(1, 1);
+        let factory: BloxFactory = Box::new(move || {
+            Box::pin(async move {
+                reply.try_send(Message::new(1, 1)).unwrap();
+            })
+        });
+        match R::spawn_with(mode, factory) {
+            Ok(()) => {
+                let mut replies = R::to_stream(reply_rx);
+                assert_eq!(replies.next().await.map(|msg| msg.payload), Some(1));
+            }
+            Err(SpawnError::Unsupported(unsupported)) => assert_eq!(unsupported, mode),
+            Err(e) => panic!("{:?} spawn failed: {}", mode, e),
+        }
+    }
+}
+
+/// The supervisor spawns the root, hands out standard channels on request, spawns
+/// bloxes and tells a parent when its child has finished
+pub async fn supervisor_handshake<R: DynamicRuntime>()
+where
+    <R::MessageHandle<StandardPayload<R>> as MessageSender>::ReceiverType: Send,
+    <R::MessageHandle<SupervisorPayload> as MessageSender>::ReceiverType: Send,
+{
+    const ROOT_ID: u16 = 1;
+    let (supervisor_handle, supervisor_rx) =
+        R::MessageHandle::<SupervisorPayload>::create_channel_with_size(11, 8);
+    let (supervisor_standard_handle, standard_receiver) =
+        R::MessageHandle::<StandardPayload<R>>::create_channel_with_size(10, 8);
+    let (root_handle, root_rx) =
+        R::MessageHandle::<StandardPayload<R>>::create_channel_with_size(ROOT_ID, 8);
+
+    // The root asks for a channel as soon as the supervisor spawns it
+    let root_supervisor_handle = supervisor_handle.clone();
+    let root_future = Box::pin(async move {
+        root_supervisor_handle
+            .try_send(Message::new(
+                ROOT_ID,
+                SupervisorPayload::RequestNewStandardHandle(4),
+            ))
+            .unwrap();
+    });
+
+    let supervisor = Blox::<SupervisorComponents<R>>::new(
+        SupervisorReceivers {
+            standard_receiver,
+            supervisor_receiver: supervisor_rx,
+        },
+        SupervisorExtendedState::new(SupervisorInitArgs {
+            root_standard_handle: root_handle,
+            root_future,
+        }),
+        SupervisorHandles {
+            standard_handle: supervisor_standard_handle,
+            supervisor_handle: supervisor_handle.clone(),
+        },
+    );
+    R::spawn(Box::new(supervisor).run());
+
+    let mut root_stream = R::to_stream(root_rx);
+    let msg = root_stream.next().await.expect("root stream ended");
+    assert_eq!(msg.source_id(), 10);
+    let StandardPayload::StandardChannel(child_handle, child_rx) = msg.payload else {
+        panic!("Expected StandardChannel, got {:?}", msg.payload);
+    };
+    let child_id = child_handle.id();
+    assert_ne!(child_id, ROOT_ID);
+    assert_ne!(child_id, 10);
+    assert_ne!(child_id, 11);
+
+    // The child reports it has finished once it has received a message on its channel
+    let child_supervisor_handle = supervisor_handle.clone();
+    let child_future = Box::pin(async move {
+        let mut child_stream = R::to_stream(child_rx);
+        if child_stream.next().await.is_some() {
+            child_supervisor_handle
+                .try_send(Message::new(child_id, SupervisorPayload::Finished))
+                .unwrap();
+        }
+    });
+    supervisor_handle
+        .try_send(Message::new(
+            ROOT_ID,
+            SupervisorPayload::Spawn(child_future),
+        ))
+        .unwrap();
+    child_handle
+        .try_send(Message::new(ROOT_ID, StandardPayload::PollState))
+        .unwrap();
+
+    let msg = root_stream.next().await.expect("root stream ended");
+    assert!(
+        matches!(msg.payload, StandardPayload::ChildFinished(id) if id == child_id),
+        "Expected ChildFinished({}), got {:?}",
+        child_id,
+        msg.payload
+    );
+}
+
+/// Generates a `#[test]` for every conformance check, running each with
+/// `$block_on`, a function that runs a future to completion within the runtime.
+///
+/// ```ignore
+/// fn block_on<F: Future>(future: F) -> F::Output { ... }
+///
+/// bloxide_core::conformance_tests!(MyRuntime, block_on);
+/// ```
+#[macro_export]
+macro_rules! conformance_tests {
+    ($runtime:ty, $block_on:path) => {
+        $crate::conformance_tests!(
+            @tests $runtime, $block_on,
+            channel_preserves_order,
+            try_send_reports_full,
+            try_send_reports_closed,
+            stream_ends_when_senders_drop,
+            spawned_task_runs,
+            spawn_with_runs_or_is_unsupported,
+            supervisor_handshake,
+        );
+    };
+    (@tests $runtime:ty, $block_on:path, $($check:ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $check() {
+                $block_on($crate::testing::conformance::$check::<$runtime>());
+            }
+        )+
+    };
+}
@@ -0,0 +1,142 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use super::recording::MockMessageHandle;
+use crate::{components::*, observer::Observer, state_machine::*, std_exports::*};
+use std::sync::{Arc, Mutex};
+
+/// An exit or entry run by a state machine under test
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateEvent<S> {
+    Exited(S),
+    Entered(S),
+}
+
+type Events<S> = Arc<Mutex<Vec<StateEvent<S>>>>;
+
+struct EventRecorder<S>(Events<S>);
+
+impl<C> Observer<C> for EventRecorder<C::States>
+where
+    C: Components,
+    C::States: Clone + Send,
+{
+    fn on_exit(&mut self, state: &C::States) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(StateEvent::Exited(state.clone()));
+    }
+
+    fn on_entry(&mut self, state: &C::States) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(StateEvent::Entered(state.clone()));
+    }
+}
+
+/// Drives a single state machine from a test, without channels, receivers or a run
+/// loop.  Build the handles with `RecordingRuntime`, feed messages with `send` and
+/// chain assertions on the outcome:
+///
+/// ```ignore
+/// harness
+///     .send(Message::new(1, CounterPayload::CountEvent(CountEvent::StartCounting)))
+///     .expect_state(CounterStateEnum::Counting(Counting))
+///     .expect_sent_to(&subscriber, |p| matches!(p, CounterPayload::SetCount(0)));
+/// ```
+///
+/// The exits and entries checked by `expect_events` are those of the last `init` or
+/// `send`.  Every assertion panics on a mismatch.
+pub struct StateMachineHarness<C: Components> {
+    pub state_machine: StateMachine<C>,
+    events: Events<C::States>,
+}
+
+impl<C> StateMachineHarness<C>
+where
+    C: Components,
+    C::States: State<C> + Clone + PartialEq + Default + Send,
+{
+    pub fn new(extended_state: C::ExtendedState, self_handles: C::Handles) -> Self {
+        let mut state_machine = StateMachine::new(extended_state, self_handles);
+        let events = Events::default();
+        state_machine.add_observer(EventRecorder(events.clone()));
+        Self {
+            state_machine,
+            events,
+        }
+    }
+
+    /// Initializes the state machine, see `StateMachine::init`
+    pub fn init(&mut self, uninit: C::States, entry_point: C::States) -> &mut Self {
+        self.events.lock().unwrap().clear();
+        self.state_machine.init(&uninit, &entry_point);
+        self
+    }
+
+    /// Dispatches a message, as the blox's run loop would
+    pub fn send(&mut self, message: impl Into<C::MessageSet>) -> &mut Self {
+        self.events.lock().unwrap().clear();
+        self.state_machine.dispatch(message.into());
+        self
+    }
+
+    #[track_caller]
+    pub fn expect_state(&mut self, expected: C::States) -> &mut Self {
+        assert_eq!(self.state_machine.current_state, expected, "current state");
+        self
+    }
+
+    /// Expects exactly these exits and entries, in order, from the last `init` or `send`
+    #[track_caller]
+    pub fn expect_events(&mut self, expected: &[StateEvent<C::States>]) -> &mut Self {
+        assert_eq!(
+            self.events.lock().unwrap().as_slice(),
+            expected,
+            "exits and entries"
+        );
+        self
+    }
+
+    #[track_caller]
+    pub fn expect_finished(&mut self) -> &mut Self {
+        assert!(
+            self.state_machine.is_finished(),
+            "state machine isn't finished, current state {:?}",
+            self.state_machine.current_state
+        );
+        self
+    }
+
+    /// Expects the oldest message sent on `handle` and not yet checked to have a
+    /// payload matching `expected`
+    #[track_caller]
+    pub fn expect_sent_to<P: Send + fmt::Debug + 'static>(
+        &mut self,
+        handle: &MockMessageHandle<P>,
+        expected: impl FnOnce(&P) -> bool,
+    ) -> &mut Self {
+        match handle.pop_sent() {
+            Some(msg) => assert!(
+                expected(&msg.payload),
+                "unexpected message sent to {:?}: {:?}",
+                handle,
+                msg
+            ),
+            None => panic!("nothing sent to {:?}", handle),
+        }
+        self
+    }
+
+    /// Expects every message sent on `handle` to have been checked already
+    #[track_caller]
+    pub fn expect_nothing_sent_to<P: Send + fmt::Debug + 'static>(
+        &mut self,
+        handle: &MockMessageHandle<P>,
+    ) -> &mut Self {
+        let sent = handle.take_sent();
+        assert!(sent.is_empty(), "sent to {:?}: {:?}", handle, sent);
+        self
+    }
+}
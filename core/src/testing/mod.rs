@@ -0,0 +1,10 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Tools for testing runtimes and bloxes, enabled by the `testing` feature
+
+pub mod conformance;
+pub mod harness;
+pub mod recording;
+
+pub use harness::*;
+pub use recording::*;
@@ -0,0 +1,210 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::{
+    components::{DynamicRuntime, Runtime},
+    messaging::*,
+    std_exports::*,
+};
+use core::task::{Context, Poll, Waker};
+use futures_core::Stream;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+type SpawnedFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+thread_local! {
+    // Futures passed to `RecordingRuntime::spawn` on this thread
+    static SPAWNED: RefCell<Vec<SpawnedFuture>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Runtime for unit tests.  Its handles record every message sent on them, and
+/// spawned futures are kept, not run, until taken with `take_spawned`.
+#[derive(Clone)]
+pub struct RecordingRuntime;
+
+impl RecordingRuntime {
+    /// Takes the futures spawned on this thread so far, oldest first
+    pub fn take_spawned() -> Vec<SpawnedFuture> {
+        SPAWNED.with(|spawned| spawned.take())
+    }
+}
+
+impl Runtime for RecordingRuntime {
+    type MessageHandle<P: Send + 'static> = MockMessageHandle<P>;
+
+    fn spawn<F>(f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        SPAWNED.with(|spawned| spawned.borrow_mut().push(Box::pin(f)));
+    }
+
+    type ReceiverStream<P: Send + 'static> = MockReceiver<P>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        receiver
+    }
+}
+
+impl DynamicRuntime for RecordingRuntime {}
+
+// Messages sent and not yet taken, shared by the handles of a channel and its receiver
+struct Channel<P> {
+    sent: VecDeque<Message<P>>,
+    senders: usize,
+    // Set to make every send fail
+    failure: Option<SendErrorKind>,
+    waker: Option<Waker>,
+}
+
+/// Handle of `RecordingRuntime`.  Sends are recorded, with their source id, to be
+/// read back with `take_sent` or from the channel's receiver.  Sends succeed unless
+/// the channel has been made to fail with `fail_sends`.
+pub struct MockMessageHandle<P: Send + 'static> {
+    id: u16,
+    channel: Arc<Mutex<Channel<P>>>,
+}
+
+impl<P: Send + 'static> MockMessageHandle<P> {
+    /// Creates a handle without a receiver, for handles a blox only sends on
+    pub fn new(id: u16) -> Self {
+        Self::create_channel_with_size(id, 0).0
+    }
+
+    /// Makes every following send on this channel, from any of its handles, fail with
+    /// `kind`, or succeed again with `None`.  `SendErrorKind::Other` fails as closed.
+    pub fn fail_sends(&self, kind: Option<SendErrorKind>) {
+        self.channel.lock().unwrap().failure = kind;
+    }
+
+    /// Takes the messages sent so far, oldest first
+    pub fn take_sent(&self) -> Vec<Message<P>> {
+        self.channel.lock().unwrap().sent.drain(..).collect()
+    }
+
+    /// Takes the oldest message sent, if any
+    pub fn pop_sent(&self) -> Option<Message<P>> {
+        self.channel.lock().unwrap().sent.pop_front()
+    }
+
+    /// Number of messages sent and not yet taken
+    pub fn sent_count(&self) -> usize {
+        self.channel.lock().unwrap().sent.len()
+    }
+}
+
+impl<P: Send + 'static> Clone for MockMessageHandle<P> {
+    fn clone(&self) -> Self {
+        self.channel.lock().unwrap().senders += 1;
+        Self {
+            id: self.id,
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<P: Send + 'static> Drop for MockMessageHandle<P> {
+    fn drop(&mut self) {
+        let mut channel = self.channel.lock().unwrap();
+        channel.senders -= 1;
+        if channel.senders == 0 {
+            if let Some(waker) = channel.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<P: Send + 'static> fmt::Debug for MockMessageHandle<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MockMessageHandle({})", self.id)
+    }
+}
+
+/// Error returned by `MockMessageHandle::try_send`, holding the message that wasn't sent
+pub enum MockSendError<P> {
+    Full(Message<P>),
+    Closed(Message<P>),
+}
+
+impl<P> fmt::Debug for MockSendError<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MockSendError::Full(_) => write!(f, "Full(..)"),
+            MockSendError::Closed(_) => write!(f, "Closed(..)"),
+        }
+    }
+}
+
+impl<P: Send + 'static> MessageSender for MockMessageHandle<P> {
+    type PayloadType = P;
+    type SenderType = MockMessageHandle<P>;
+    type ReceiverType = MockReceiver<P>;
+    type ErrorType = MockSendError<P>;
+
+    fn try_send(&self, msg: Message<P>) -> Result<(), Self::ErrorType> {
+        let mut channel = self.channel.lock().unwrap();
+        match channel.failure {
+            Some(SendErrorKind::Full) => return Err(MockSendError::Full(msg)),
+            Some(_) => return Err(MockSendError::Closed(msg)),
+            None => {}
+        }
+        channel.sent.push_back(msg);
+        if let Some(waker) = channel.waker.take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    fn error_kind(error: &Self::ErrorType) -> SendErrorKind {
+        match error {
+            MockSendError::Full(_) => SendErrorKind::Full,
+            MockSendError::Closed(_) => SendErrorKind::Closed,
+        }
+    }
+
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    // Recording channels are unbounded, the size is ignored
+    fn create_channel_with_size(id: u16, _size: usize) -> (Self, Self::ReceiverType) {
+        let channel = Arc::new(Mutex::new(Channel {
+            sent: VecDeque::new(),
+            senders: 1,
+            failure: None,
+            waker: None,
+        }));
+        (
+            Self {
+                id,
+                channel: channel.clone(),
+            },
+            MockReceiver { channel },
+        )
+    }
+}
+
+/// Receiving side of a `MockMessageHandle` channel, ends once every handle has been
+/// dropped and every message has been received
+pub struct MockReceiver<P: Send + 'static> {
+    channel: Arc<Mutex<Channel<P>>>,
+}
+
+impl<P: Send + 'static> Stream for MockReceiver<P> {
+    type Item = Message<P>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut channel = self.channel.lock().unwrap();
+        if let Some(msg) = channel.sent.pop_front() {
+            return Poll::Ready(Some(msg));
+        }
+        if channel.senders == 0 {
+            return Poll::Ready(None);
+        }
+        channel.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use crate::{messaging::*, std_exports::*};
+use log::*;
+use serde::{Deserialize, Serialize};
+
+/// What a state machine does with a message that reaches the root state unhandled
+#[derive(Default)]
+pub enum UnhandledPolicy {
+    /// Drop the message silently
+    Ignore,
+    /// Drop the message and log a warning
+    #[default]
+    Log,
+    /// Send a `DeadLetter` describing the message to a sink, usually a dead-letter blox
+    #[cfg(feature = "alloc")]
+    DeadLetter(Box<dyn DeadLetterSink>),
+    /// Treat the message as a `StateError::Unhandled`, routing to the blox's error state
+    Error,
+}
+
+impl fmt::Debug for UnhandledPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnhandledPolicy::Ignore => write!(f, "Ignore"),
+            UnhandledPolicy::Log => write!(f, "Log"),
+            #[cfg(feature = "alloc")]
+            UnhandledPolicy::DeadLetter(_) => write!(f, "DeadLetter"),
+            UnhandledPolicy::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// Description of a message no state handled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Id of the blox that sent the message
+    pub source_id: u16,
+    /// Path from the root state down to the current state when the message arrived
+    pub state_path: StateVec<ErrorString>,
+    /// Debug rendering of the message
+    pub message: ErrorString,
+}
+
+/// Receives dead letters.  Implemented for any handle carrying `DeadLetter` payloads,
+/// so a dead-letter blox's handle can be used directly
+pub trait DeadLetterSink: Send {
+    fn send_dead_letter(&self, letter: DeadLetter);
+}
+
+impl<H> DeadLetterSink for H
+where
+    H: MessageSender<PayloadType = DeadLetter> + Send,
+{
+    fn send_dead_letter(&self, letter: DeadLetter) {
+        if let Err(e) = self.try_send(Message::new(letter.source_id, letter)) {
+            error!("Failed to send dead letter: {:?}", e);
+        }
+    }
+}
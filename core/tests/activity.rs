@@ -0,0 +1,185 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+// Do-activities started by states, run by polling the futures `RecordingRuntime` keeps
+
+use bloxide_core::{components::*, messaging::*, state_machine::*, testing::*};
+use std::future::{pending, ready, Future};
+use std::pin::Pin;
+use std::task::{Context, Waker};
+
+type Harness = StateMachineHarness<ActivityComponents>;
+
+struct ActivityComponents;
+
+impl Components for ActivityComponents {
+    type States = ActivityState;
+    type MessageSet = Goto;
+    type ExtendedState = NoState;
+    type Receivers = ();
+    // Activity results are sent here
+    type Handles = MockMessageHandle<u32>;
+}
+
+struct NoState;
+
+impl ExtendedState for NoState {
+    type InitArgs = ();
+    fn new(_args: Self::InitArgs) -> Self {
+        NoState
+    }
+}
+
+#[derive(Debug)]
+struct Goto(ActivityState);
+
+impl MessageSet for Goto {
+    fn source_id(&self) -> u16 {
+        1
+    }
+}
+
+// Uninit
+// ├── Outer
+// │   ├── Middle
+// │   │   └── Ready   runs an activity finishing at once
+// │   └── Sibling
+// └── Waiting         runs an activity that never finishes
+//
+// Middle starts an activity that never finishes when it exits
+#[derive(Clone, PartialEq, Debug, Default)]
+enum ActivityState {
+    #[default]
+    Uninit,
+    Outer,
+    Middle,
+    Ready,
+    Sibling,
+    Waiting,
+}
+
+impl StateEnum for ActivityState {
+    fn all_states() -> &'static [Self] {
+        &[
+            ActivityState::Uninit,
+            ActivityState::Outer,
+            ActivityState::Middle,
+            ActivityState::Ready,
+            ActivityState::Sibling,
+            ActivityState::Waiting,
+        ]
+    }
+}
+
+impl State<ActivityComponents> for ActivityState {
+    fn parent(&self) -> ActivityState {
+        match self {
+            ActivityState::Middle | ActivityState::Sibling => ActivityState::Outer,
+            ActivityState::Ready => ActivityState::Middle,
+            _ => ActivityState::Uninit,
+        }
+    }
+
+    fn on_entry(&self, state_machine: &mut StateMachine<ActivityComponents>) {
+        let handle = state_machine.self_handles.clone();
+        match self {
+            ActivityState::Ready => {
+                state_machine.start_activity::<RecordingRuntime, _, _>(&handle, ready(7))
+            }
+            ActivityState::Waiting => {
+                state_machine.start_activity::<RecordingRuntime, _, _>(&handle, pending())
+            }
+            _ => {}
+        }
+    }
+
+    fn on_exit(&self, state_machine: &mut StateMachine<ActivityComponents>) {
+        if *self == ActivityState::Middle {
+            let handle = state_machine.self_handles.clone();
+            state_machine.start_activity::<RecordingRuntime, _, _>(&handle, pending());
+        }
+    }
+
+    fn handle_message(
+        &self,
+        _state_machine: &mut StateMachine<ActivityComponents>,
+        message: Goto,
+    ) -> Option<Transition<ActivityState, Goto>> {
+        Some(Transition::To(message.0))
+    }
+}
+
+// A harness in `state`, and the handle activity results are sent to
+fn harness_in(state: ActivityState) -> (Harness, MockMessageHandle<u32>) {
+    let results = MockMessageHandle::new(2);
+    let mut harness = Harness::new(NoState, results.clone());
+    harness.init(ActivityState::Uninit, state);
+    (harness, results)
+}
+
+type SpawnedFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+// Futures spawned on `RecordingRuntime` and not completed yet
+#[derive(Default)]
+struct Spawned(Vec<SpawnedFuture>);
+
+impl Spawned {
+    // Polls every future spawned so far once, returns how many completed
+    fn poll(&mut self) -> usize {
+        self.0.extend(RecordingRuntime::take_spawned());
+        let mut context = Context::from_waker(Waker::noop());
+        let before = self.0.len();
+        self.0
+            .retain_mut(|future| future.as_mut().poll(&mut context).is_pending());
+        before - self.0.len()
+    }
+}
+
+#[test]
+fn activity_result_is_sent_to_the_blox() {
+    let mut spawned = Spawned::default();
+    let (mut harness, results) = harness_in(ActivityState::Ready);
+    assert_eq!(harness.state_machine.activity_count(), 1);
+    assert_eq!(spawned.poll(), 1);
+
+    let sent = results.take_sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!((sent[0].source_id, sent[0].payload), (2, 7));
+    assert_eq!(harness.state_machine.activity_count(), 0);
+    // The finished activity is dropped, its state is still active
+    harness
+        .send(Goto(ActivityState::Ready))
+        .expect_state(ActivityState::Ready);
+    assert_eq!(harness.state_machine.activity_count(), 0);
+}
+
+#[test]
+fn activity_is_aborted_when_its_state_exits() {
+    let mut spawned = Spawned::default();
+    let (mut harness, results) = harness_in(ActivityState::Waiting);
+    assert_eq!(harness.state_machine.activity_count(), 1);
+    harness.send(Goto(ActivityState::Sibling));
+    assert_eq!(harness.state_machine.activity_count(), 0);
+
+    // The aborted activity completes without sending anything
+    assert_eq!(spawned.poll(), 1);
+    assert_eq!(results.sent_count(), 0);
+}
+
+#[test]
+fn activity_started_on_exit_belongs_to_the_parent() {
+    let mut spawned = Spawned::default();
+    let (mut harness, results) = harness_in(ActivityState::Ready);
+    assert_eq!(spawned.poll(), 1);
+    results.take_sent();
+
+    // Middle's exit activity belongs to Outer, still active in Sibling
+    harness.send(Goto(ActivityState::Sibling));
+    assert_eq!(harness.state_machine.activity_count(), 1);
+    assert_eq!(spawned.poll(), 0);
+
+    // Exiting Outer aborts it
+    harness.send(Goto(ActivityState::Waiting));
+    assert_eq!(harness.state_machine.activity_count(), 1);
+    assert_eq!(spawned.poll(), 1);
+    assert_eq!(results.sent_count(), 0);
+}
@@ -0,0 +1,15 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+// Also run without alloc, where `ErrorString` has a fixed capacity:
+//     cargo test -p bloxide-core --no-default-features --test bounded
+
+use bloxide_core::bounded::*;
+
+#[test]
+fn error_string_keeps_what_fits() {
+    let long = "x".repeat(2 * ERROR_STRING_CAPACITY);
+    let error = error_string(format_args!("error: {}", long));
+    let expected = format!("error: {}", long);
+    let kept = expected.len().min(error.capacity());
+    assert_eq!(error.as_str(), &expected[..kept]);
+}
@@ -0,0 +1,89 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{blox::demo_counter::messaging::*, codec::*, messaging::*};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Reading {
+    sensor: String,
+    celsius: f32,
+}
+
+bloxide_core::wire_tags! {
+    Reading = 100,
+}
+
+fn round_trip<C: Codec>() {
+    let message = Message::new(5, CounterPayload::CountEvent(CountEvent::Reset));
+    let raw = message.to_raw::<C>(9).unwrap();
+    assert_eq!((raw.to, raw.from), (9, 5));
+    assert_eq!(wire_tag(&raw.payload), Some(CounterPayload::WIRE_TAG));
+    let decoded = Message::<CounterPayload>::from_raw::<C>(&raw).unwrap();
+    assert_eq!(decoded.source_id, 5);
+    assert!(matches!(
+        decoded.payload,
+        CounterPayload::CountEvent(CountEvent::Reset)
+    ));
+
+    let reading = Message::new(
+        1,
+        Reading {
+            sensor: "boiler".to_string(),
+            celsius: 71.5,
+        },
+    );
+    let raw = reading.to_raw::<C>(2).unwrap();
+    assert_eq!(wire_tag(&raw.payload), Some(100));
+    let decoded = Message::<Reading>::from_raw::<C>(&raw).unwrap();
+    assert_eq!(decoded.payload, reading.payload);
+
+    // A reading isn't a counter payload, whatever the bytes would decode to
+    assert_eq!(
+        Message::<CounterPayload>::from_raw::<C>(&raw).unwrap_err(),
+        CodecError::WrongTag {
+            expected: 1,
+            found: 100
+        }
+    );
+}
+
+#[test]
+fn postcard_round_trips() {
+    round_trip::<Postcard>();
+}
+
+#[test]
+fn cbor_round_trips() {
+    round_trip::<Cbor>();
+}
+
+#[test]
+fn json_round_trips() {
+    round_trip::<Json>();
+    let raw = Message::new(1, CounterPayload::Increment(3))
+        .to_raw::<Json>(2)
+        .unwrap();
+    assert_eq!(&raw.payload[2..], br#"{"Increment":3}"#);
+}
+
+#[test]
+fn malformed_payloads_are_rejected() {
+    let raw = RawPayload {
+        to: 1,
+        from: 2,
+        payload: vec![1],
+    };
+    assert_eq!(
+        Message::<CounterPayload>::from_raw::<Postcard>(&raw).unwrap_err(),
+        CodecError::MissingTag
+    );
+
+    let mut raw = Message::new(1, CounterPayload::SetMax(300))
+        .to_raw::<Postcard>(2)
+        .unwrap();
+    raw.payload.truncate(raw.payload.len() - 1);
+    assert!(matches!(
+        Message::<CounterPayload>::from_raw::<Postcard>(&raw),
+        Err(CodecError::Decode(_))
+    ));
+}
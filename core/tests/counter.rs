@@ -0,0 +1,143 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::demo_counter::{components::*, ext_state::*, messaging::*, states::*},
+    messaging::*,
+    state_machine::*,
+    testing::*,
+};
+
+type Harness = StateMachineHarness<CounterComponents<RecordingRuntime>>;
+
+const CONTROLLER_ID: u16 = 1;
+
+// A counter in NotStarted with one subscriber
+fn started_counter() -> (Harness, MockMessageHandle<CounterPayload>) {
+    let mut harness = Harness::new(
+        CounterExtendedState::new(CounterInitArgs {
+            supervisor_handle: MockMessageHandle::new(11),
+        }),
+        CounterHandles {
+            standard_handle: MockMessageHandle::new(2),
+            counter_handle: MockMessageHandle::new(3),
+        },
+    );
+    let subscriber = MockMessageHandle::new(CONTROLLER_ID);
+    harness
+        .state_machine
+        .extended_state
+        .subscribers
+        .push(subscriber.clone());
+    harness.init(
+        CounterStateEnum::Uninit(Uninit),
+        CounterStateEnum::NotStarted(NotStarted),
+    );
+    (harness, subscriber)
+}
+
+fn counter_message(payload: CounterPayload) -> Message<CounterPayload> {
+    Message::new(CONTROLLER_ID, payload)
+}
+
+#[test]
+fn init_enters_not_started_through_idle() {
+    let (mut harness, subscriber) = started_counter();
+    harness
+        .expect_state(CounterStateEnum::NotStarted(NotStarted))
+        .expect_events(&[
+            StateEvent::Entered(CounterStateEnum::Idle(Idle)),
+            StateEvent::Entered(CounterStateEnum::NotStarted(NotStarted)),
+        ])
+        .expect_nothing_sent_to(&subscriber);
+}
+
+#[test]
+fn start_counting_notifies_subscribers() {
+    let (mut harness, subscriber) = started_counter();
+    harness
+        .send(counter_message(CounterPayload::SetCount(3)))
+        .expect_state(CounterStateEnum::NotStarted(NotStarted))
+        .expect_events(&[])
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::StartCounting,
+        )))
+        .expect_state(CounterStateEnum::Counting(Counting))
+        .expect_events(&[
+            StateEvent::Exited(CounterStateEnum::NotStarted(NotStarted)),
+            StateEvent::Exited(CounterStateEnum::Idle(Idle)),
+            StateEvent::Entered(CounterStateEnum::Counting(Counting)),
+        ])
+        .expect_sent_to(&subscriber, |p| matches!(p, CounterPayload::SetCount(3)))
+        .expect_nothing_sent_to(&subscriber);
+}
+
+#[test]
+fn reaching_max_finishes() {
+    let (mut harness, subscriber) = started_counter();
+    harness
+        .send(counter_message(CounterPayload::SetMax(2)))
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::StartCounting,
+        )))
+        .expect_sent_to(&subscriber, |p| matches!(p, CounterPayload::SetCount(0)))
+        .send(counter_message(CounterPayload::Increment(1)))
+        .expect_state(CounterStateEnum::Counting(Counting))
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::GetCount,
+        )))
+        .expect_sent_to(&subscriber, |p| matches!(p, CounterPayload::SetCount(1)))
+        .send(counter_message(CounterPayload::Increment(1)))
+        .expect_state(CounterStateEnum::Finished(Finished))
+        .expect_events(&[
+            StateEvent::Exited(CounterStateEnum::Counting(Counting)),
+            StateEvent::Entered(CounterStateEnum::Finished(Finished)),
+        ])
+        .expect_finished();
+}
+
+#[test]
+fn reset_returns_to_not_started() {
+    let (mut harness, _subscriber) = started_counter();
+    harness
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::StartCounting,
+        )))
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::Reset,
+        )))
+        .expect_state(CounterStateEnum::NotStarted(NotStarted))
+        .expect_events(&[
+            StateEvent::Exited(CounterStateEnum::Counting(Counting)),
+            StateEvent::Entered(CounterStateEnum::Idle(Idle)),
+            StateEvent::Entered(CounterStateEnum::NotStarted(NotStarted)),
+        ]);
+}
+
+#[test]
+fn underflow_enters_error_state() {
+    let (mut harness, _subscriber) = started_counter();
+    harness
+        .send(counter_message(CounterPayload::SetCount(1)))
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::StartCounting,
+        )))
+        .send(counter_message(CounterPayload::Decrement(2)))
+        .expect_state(CounterStateEnum::Error(Error));
+    assert!(harness.state_machine.last_error().is_some());
+}
+
+#[test]
+fn overflow_enters_error_state() {
+    let (mut harness, _subscriber) = started_counter();
+    harness
+        .send(counter_message(CounterPayload::SetCount(usize::MAX)))
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::StartCounting,
+        )))
+        .send(counter_message(CounterPayload::Increment(1)))
+        .expect_state(CounterStateEnum::Error(Error));
+    assert_eq!(
+        harness.state_machine.last_error(),
+        Some(&StateError::other("Count overflow"))
+    );
+}
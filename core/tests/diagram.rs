@@ -0,0 +1,128 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::{
+        demo_counter::{components::*, ext_state::*, messaging::*, states::*},
+        supervisor::{components::SupervisorComponents, states as supervisor},
+    },
+    diagram::*,
+    messaging::*,
+    state_machine::*,
+    testing::*,
+};
+
+type Components = CounterComponents<RecordingRuntime>;
+
+#[test]
+fn counter_mermaid_shows_hierarchy_and_declared_transitions() {
+    let diagram = StateDiagram::new::<Components>("Counter")
+        .with_initial(&CounterStateEnum::NotStarted(NotStarted));
+    let expected = "\
+---
+title: Counter
+---
+stateDiagram-v2
+    state Idle {
+        NotStarted
+        Error
+    }
+    Counting
+    Finished
+    [*] --> NotStarted
+    NotStarted --> Counting
+    Counting --> Finished
+    Counting --> NotStarted
+    Counting --> Error
+    Finished --> [*]
+";
+    assert_eq!(diagram.mermaid(), expected);
+}
+
+#[test]
+fn supervisor_declares_its_transitions() {
+    let mermaid = StateDiagram::new::<SupervisorComponents<RecordingRuntime>>("Supervisor")
+        .with_initial(&supervisor::SupervisorStateEnum::Running(
+            supervisor::Running,
+        ))
+        .mermaid();
+    assert!(mermaid.contains("    [*] --> Running\n    Running --> Error\n"));
+}
+
+#[test]
+fn dot_draws_composites_as_clusters() {
+    let dot = StateDiagram::new::<Components>("Counter")
+        .with_transitions([(
+            &CounterStateEnum::Finished(Finished),
+            &CounterStateEnum::Idle(Idle),
+        )])
+        .dot();
+    assert!(dot.starts_with("digraph \"Counter\" {\n"));
+    assert!(dot.contains("    subgraph \"cluster_Idle\" {\n"));
+    assert!(dot.contains("        \"NotStarted\";\n"));
+    assert!(dot.contains("    \"Finished\" [peripheries=2];\n"));
+    assert!(dot.contains("    \"Finished\" -> \"Idle\" [lhead=\"cluster_Idle\"];\n"));
+    assert!(dot.ends_with("}\n"));
+}
+
+#[test]
+fn plantuml_is_delimited() {
+    let plantuml = StateDiagram::new::<Components>("Counter").plantuml();
+    assert!(plantuml.starts_with("@startuml\ntitle Counter\n"));
+    assert!(plantuml.contains("    state Idle {\n        state NotStarted\n"));
+    assert!(plantuml.ends_with("@enduml\n"));
+}
+
+#[test]
+fn collected_transitions_are_drawn() {
+    let mut harness = StateMachineHarness::<Components>::new(
+        CounterExtendedState::new(CounterInitArgs {
+            supervisor_handle: MockMessageHandle::new(11),
+        }),
+        CounterHandles {
+            standard_handle: MockMessageHandle::new(2),
+            counter_handle: MockMessageHandle::new(3),
+        },
+    );
+    let collector = TransitionCollector::new();
+    harness.state_machine.add_observer(collector.clone());
+    harness
+        .init(
+            CounterStateEnum::Uninit(Uninit),
+            CounterStateEnum::NotStarted(NotStarted),
+        )
+        .send(Message::new(
+            1,
+            CounterPayload::CountEvent(CountEvent::StartCounting),
+        ))
+        .send(Message::new(
+            1,
+            CounterPayload::CountEvent(CountEvent::Reset),
+        ));
+
+    assert_eq!(
+        collector.transitions(),
+        vec![
+            (
+                CounterStateEnum::Uninit(Uninit),
+                CounterStateEnum::NotStarted(NotStarted)
+            ),
+            (
+                CounterStateEnum::NotStarted(NotStarted),
+                CounterStateEnum::Counting(Counting)
+            ),
+            (
+                CounterStateEnum::Counting(Counting),
+                CounterStateEnum::NotStarted(NotStarted)
+            ),
+        ]
+    );
+
+    // Added to the declared transitions: the initial transition was only observed,
+    // Counting --> NotStarted was both declared and observed and is drawn once
+    let transitions = collector.transitions();
+    let mermaid = StateDiagram::new::<Components>("Counter")
+        .with_transitions(transitions.iter().map(|(from, to)| (from, to)))
+        .mermaid();
+    assert!(mermaid.contains("    [*] --> NotStarted\n"));
+    assert_eq!(mermaid.matches("Counting --> NotStarted").count(), 1);
+}
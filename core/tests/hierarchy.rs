@@ -0,0 +1,332 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+// Property tests of hierarchical transitions on randomly generated state hierarchies.
+// Every exit and entry is checked against a reference model of the LCA rule.
+
+use bloxide_core::{components::*, messaging::*, observer::Observer, state_machine::*};
+use proptest::prelude::*;
+use proptest::sample::Index;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+const MAX_STATES: usize = 24;
+
+thread_local! {
+    // The hierarchy of the current test case, `PARENTS[i]` is the parent of `Node(i)`.
+    // Node 0 is the root
+    static PARENTS: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    static ALL_STATES: RefCell<&'static [Node]> = const { RefCell::new(&[]) };
+}
+
+// Nodes outside the hierarchy are their own parent
+fn parent_of(node: usize) -> usize {
+    PARENTS.with(|parents| parents.borrow().get(node).copied().unwrap_or(node))
+}
+
+// Sets the hierarchy used by state machines created on this thread
+fn set_hierarchy(parents: Vec<usize>) {
+    let states: Vec<Node> = (0..parents.len()).map(Node).collect();
+    ALL_STATES.with(|all| *all.borrow_mut() = Box::leak(states.into_boxed_slice()));
+    PARENTS.with(|p| *p.borrow_mut() = parents);
+}
+
+struct TestComponents;
+
+impl Components for TestComponents {
+    type ExtendedState = NoState;
+    type States = Node;
+    type MessageSet = Goto;
+    type Receivers = ();
+    type Handles = ();
+}
+
+struct NoState;
+
+impl ExtendedState for NoState {
+    type InitArgs = ();
+    fn new(_args: Self::InitArgs) -> Self {
+        NoState
+    }
+}
+
+// Transitions to `target` once forwarded to the parent `forward` times
+#[derive(Debug)]
+struct Goto {
+    target: usize,
+    forward: usize,
+}
+
+impl MessageSet for Goto {
+    fn source_id(&self) -> u16 {
+        0
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+struct Node(usize);
+
+impl StateEnum for Node {
+    fn all_states() -> &'static [Self] {
+        ALL_STATES.with(|all| *all.borrow())
+    }
+}
+
+impl State<TestComponents> for Node {
+    fn parent(&self) -> Node {
+        Node(parent_of(self.0))
+    }
+
+    fn handle_message(
+        &self,
+        _state_machine: &mut StateMachine<TestComponents>,
+        message: Goto,
+    ) -> Option<Transition<Node, Goto>> {
+        if message.forward > 0 {
+            return Some(Transition::Parent(Goto {
+                target: message.target,
+                forward: message.forward - 1,
+            }));
+        }
+        Some(Transition::To(Node(message.target)))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Event {
+    Exited(usize),
+    Entered(usize),
+}
+
+#[derive(Clone, Default)]
+struct EventLog(Arc<Mutex<Vec<Event>>>);
+
+impl EventLog {
+    fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+impl Observer<TestComponents> for EventLog {
+    fn on_exit(&mut self, state: &Node) {
+        self.0.lock().unwrap().push(Event::Exited(state.0));
+    }
+
+    fn on_entry(&mut self, state: &Node) {
+        self.0.lock().unwrap().push(Event::Entered(state.0));
+    }
+}
+
+// Path from the root down to `node`, both included
+fn path(parents: &[usize], mut node: usize) -> Vec<usize> {
+    let mut path = vec![node];
+    while parents[node] != node {
+        node = parents[node];
+        path.push(node);
+    }
+    path.reverse();
+    path
+}
+
+// Reference model: exit the current path below the longest common prefix of both
+// paths, innermost first, then enter the target path below it, outermost first
+fn expected_events(parents: &[usize], from: usize, to: usize) -> Vec<Event> {
+    let from_path = path(parents, from);
+    let to_path = path(parents, to);
+    let common = from_path
+        .iter()
+        .zip(&to_path)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let exits = from_path[common..].iter().rev().map(|s| Event::Exited(*s));
+    let entries = to_path[common..].iter().map(|s| Event::Entered(*s));
+    exits.chain(entries).collect()
+}
+
+// Checks exits and entries come in balanced pairs: only the innermost active state is
+// exited, only children of the innermost active state are entered, and the active
+// states end up being the path to the current state
+fn check_balanced(parents: &[usize], active: &mut Vec<usize>, events: &[Event], current: usize) {
+    for event in events {
+        match event {
+            Event::Exited(state) => {
+                assert_eq!(active.pop(), Some(*state), "exited a non-innermost state");
+            }
+            Event::Entered(state) => {
+                assert_eq!(
+                    active.last(),
+                    Some(&parents[*state]),
+                    "entered {} outside its parent",
+                    state
+                );
+                active.push(*state);
+            }
+        }
+    }
+    assert_eq!(*active, path(parents, current), "active states");
+}
+
+#[derive(Clone, Debug)]
+enum Step {
+    // Transition to any non-root state
+    To(Index),
+    // Transition to the current state
+    ToCurrent,
+    // Transition to a non-root ancestor of the current state, if it has one
+    ToAncestor(Index),
+}
+
+fn step() -> impl Strategy<Value = (Step, Index)> {
+    let step = prop_oneof![
+        any::<Index>().prop_map(Step::To),
+        Just(Step::ToCurrent),
+        any::<Index>().prop_map(Step::ToAncestor),
+    ];
+    (step, any::<Index>())
+}
+
+// Parents of nodes 1.., each parent has a lower index so the hierarchy is a tree
+fn hierarchy() -> impl Strategy<Value = Vec<usize>> {
+    (2..=MAX_STATES).prop_flat_map(|len| {
+        (1..len)
+            .map(|node| 0..node)
+            .collect::<Vec<_>>()
+            .prop_map(|parents| [0].into_iter().chain(parents).collect())
+    })
+}
+
+proptest! {
+    #[test]
+    fn transitions_match_reference_model(
+        parents in hierarchy(),
+        initial in any::<Index>(),
+        steps in prop::collection::vec(step(), 1..40),
+    ) {
+        set_hierarchy(parents.clone());
+        let log = EventLog::default();
+        let mut state_machine = StateMachine::<TestComponents>::new(NoState, ());
+        state_machine.add_observer(log.clone());
+        prop_assert!(state_machine.validate().is_ok());
+
+        let mut current = 1 + initial.index(parents.len() - 1);
+        state_machine.init(&Node(0), &Node(current));
+        let events = log.take();
+        prop_assert_eq!(&events, &expected_events(&parents, 0, current));
+        let mut active = vec![0];
+        check_balanced(&parents, &mut active, &events, current);
+
+        for (step, forward) in steps {
+            let current_path = path(&parents, current);
+            let target = match step {
+                Step::To(index) => 1 + index.index(parents.len() - 1),
+                Step::ToCurrent => current,
+                Step::ToAncestor(index) => {
+                    // Non-root proper ancestors of the current state
+                    let ancestors = &current_path[1..current_path.len() - 1];
+                    if ancestors.is_empty() {
+                        continue;
+                    }
+                    *index.get(ancestors)
+                }
+            };
+            // Any non-root state on the current path may be the one handling it
+            let forward = forward.index(current_path.len() - 1);
+
+            state_machine.dispatch(Goto { target, forward });
+            let events = log.take();
+            prop_assert_eq!(&events, &expected_events(&parents, current, target));
+            prop_assert_eq!(&state_machine.current_state, &Node(target));
+            check_balanced(&parents, &mut active, &events, target);
+
+            match step {
+                // A self-transition is local, nothing is exited or re-entered
+                Step::ToCurrent => prop_assert!(events.is_empty()),
+                // A transition to an ancestor only exits the states below it
+                Step::ToAncestor(_) => {
+                    let below = &current_path[current_path.iter().position(|s| *s == target).unwrap() + 1..];
+                    let exits: Vec<Event> = below.iter().rev().map(|s| Event::Exited(*s)).collect();
+                    prop_assert_eq!(&events, &exits);
+                }
+                Step::To(_) => {}
+            }
+            current = target;
+        }
+        prop_assert!(state_machine.last_error().is_none());
+    }
+}
+
+// Errors found validating the hierarchy `parents`
+fn validation_errors(parents: Vec<usize>) -> Vec<ValidationError<Node>> {
+    set_hierarchy(parents);
+    let state_machine = StateMachine::<TestComponents>::new(NoState, ());
+    state_machine
+        .validate()
+        .expect_err("hierarchy is invalid")
+        .into_iter()
+        .collect()
+}
+
+fn nodes(nodes: &[usize]) -> Vec<Node> {
+    nodes.iter().copied().map(Node).collect()
+}
+
+#[test]
+fn no_states() {
+    assert_eq!(validation_errors(vec![]), [ValidationError::NoStates]);
+}
+
+#[test]
+fn no_root() {
+    let errors = validation_errors(vec![1, 0]);
+    assert!(errors.contains(&ValidationError::NoRoot), "{:?}", errors);
+}
+
+#[test]
+fn multiple_roots() {
+    assert_eq!(
+        validation_errors(vec![0, 1, 0]),
+        [ValidationError::MultipleRoots(nodes(&[0, 1]))]
+    );
+}
+
+#[test]
+fn unknown_parent() {
+    let errors = validation_errors(vec![0, 0, 5]);
+    assert!(
+        errors.contains(&ValidationError::UnknownParent {
+            state: Node(2),
+            parent: Node(5),
+        }),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn cycle() {
+    assert_eq!(
+        validation_errors(vec![0, 2, 1]),
+        [
+            ValidationError::Cycle(nodes(&[1, 2])),
+            ValidationError::Unreachable(nodes(&[1, 2])),
+        ]
+    );
+}
+
+#[test]
+fn unreachable() {
+    // 1's parent isn't a state, so neither 1 nor its child 2 reach the root
+    let errors = validation_errors(vec![0, 3, 1]);
+    assert!(
+        errors.contains(&ValidationError::Unreachable(nodes(&[1, 2]))),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+#[should_panic(expected = "Undeclared(Node(7))")]
+fn init_rejects_undeclared_entry_point() {
+    set_hierarchy(vec![0, 0]);
+    let mut state_machine = StateMachine::<TestComponents>::new(NoState, ());
+    state_machine.init(&Node(0), &Node(7));
+}
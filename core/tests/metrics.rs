@@ -0,0 +1,209 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::demo_counter::{components::*, ext_state::*, messaging::*, states::*},
+    components::*,
+    messaging::*,
+    metrics::*,
+    state_machine::*,
+    testing::*,
+};
+use futures_core::Stream;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
+#[test]
+fn in_memory_metrics_render_as_prometheus() {
+    let metrics = InMemoryMetrics::new();
+    metrics.increment_counter(TRANSITIONS_TOTAL, &[("blox", "a"), ("to", "B")], 2);
+    metrics.increment_counter(TRANSITIONS_TOTAL, &[("blox", "a"), ("to", "B")], 1);
+    metrics.set_gauge(MAILBOX_DEPTH, &[("channel", "say \"hi\"")], 4.0);
+    metrics.record_histogram(DISPATCH_SECONDS, &[("blox", "a")], 5e-6);
+    metrics.record_histogram(DISPATCH_SECONDS, &[("blox", "a")], 0.5);
+    metrics.record_histogram(DISPATCH_SECONDS, &[("blox", "a")], 2.0);
+
+    assert_eq!(
+        metrics.counter(TRANSITIONS_TOTAL, &[("blox", "a"), ("to", "B")]),
+        3
+    );
+    assert_eq!(metrics.counter(TRANSITIONS_TOTAL, &[("blox", "b")]), 0);
+    let histogram = metrics
+        .histogram(DISPATCH_SECONDS, &[("blox", "a")])
+        .unwrap();
+    assert_eq!(histogram.buckets, vec![0, 1, 0, 0, 0, 0, 1]);
+    assert_eq!(histogram.count, 3);
+
+    let expected = "\
+# TYPE bloxide_dispatch_seconds histogram
+bloxide_dispatch_seconds_bucket{blox=\"a\",le=\"0.000001\"} 0
+bloxide_dispatch_seconds_bucket{blox=\"a\",le=\"0.00001\"} 1
+bloxide_dispatch_seconds_bucket{blox=\"a\",le=\"0.0001\"} 1
+bloxide_dispatch_seconds_bucket{blox=\"a\",le=\"0.001\"} 1
+bloxide_dispatch_seconds_bucket{blox=\"a\",le=\"0.01\"} 1
+bloxide_dispatch_seconds_bucket{blox=\"a\",le=\"0.1\"} 1
+bloxide_dispatch_seconds_bucket{blox=\"a\",le=\"1\"} 2
+bloxide_dispatch_seconds_bucket{blox=\"a\",le=\"+Inf\"} 3
+bloxide_dispatch_seconds_sum{blox=\"a\"} 2.500005
+bloxide_dispatch_seconds_count{blox=\"a\"} 3
+# TYPE bloxide_mailbox_depth gauge
+bloxide_mailbox_depth{channel=\"say \\\"hi\\\"\"} 4
+# TYPE bloxide_transitions_total counter
+bloxide_transitions_total{blox=\"a\",to=\"B\"} 3
+";
+    assert_eq!(metrics.prometheus(), expected);
+}
+
+#[test]
+fn observer_reports_dispatch_latency_and_transitions() {
+    let metrics = Arc::new(InMemoryMetrics::new());
+    let mut harness = StateMachineHarness::<CounterComponents<RecordingRuntime>>::new(
+        CounterExtendedState::new(CounterInitArgs {
+            supervisor_handle: MockMessageHandle::new(11),
+        }),
+        CounterHandles {
+            standard_handle: MockMessageHandle::new(2),
+            counter_handle: MockMessageHandle::new(3),
+        },
+    );
+    harness
+        .state_machine
+        .add_observer(MetricsObserver::new("counter", metrics.clone()));
+    harness
+        .init(
+            CounterStateEnum::Uninit(Uninit),
+            CounterStateEnum::NotStarted(NotStarted),
+        )
+        .send(Message::new(1, CounterPayload::SetMax(1)))
+        .send(Message::new(
+            1,
+            CounterPayload::CountEvent(CountEvent::StartCounting),
+        ))
+        .send(Message::new(1, CounterPayload::Increment(1)))
+        .expect_state(CounterStateEnum::Finished(Finished));
+
+    let transition = |from, to| {
+        metrics.counter(
+            TRANSITIONS_TOTAL,
+            &[("blox", "counter"), ("from", from), ("to", to)],
+        )
+    };
+    assert_eq!(transition("Uninit", "NotStarted"), 1);
+    assert_eq!(transition("NotStarted", "Counting"), 1);
+    assert_eq!(transition("Counting", "Finished"), 1);
+
+    let dispatches = |state| {
+        metrics
+            .histogram(DISPATCH_SECONDS, &[("blox", "counter"), ("state", state)])
+            .map_or(0, |h| h.count)
+    };
+    assert_eq!(dispatches("NotStarted"), 2);
+    assert_eq!(dispatches("Counting"), 1);
+}
+
+// The only test using the global sink, the other channels here have their own
+#[test]
+fn metered_channels_report_depth_receives_and_failures() {
+    let metrics = Arc::new(InMemoryMetrics::new());
+    set_sink(Some(metrics.clone()));
+
+    type Handle = <Metered<RecordingRuntime> as Runtime>::MessageHandle<CounterPayload>;
+    let (handle, receiver) = Handle::create_channel_with_size(7, 8);
+    let depth = || metrics.gauge(MAILBOX_DEPTH, &[("channel", "7")]);
+
+    handle
+        .try_send(Message::new(1, CounterPayload::Increment(1)))
+        .unwrap();
+    handle
+        .try_send(Message::new(1, CounterPayload::Increment(2)))
+        .unwrap();
+    assert_eq!(depth(), Some(2.0));
+
+    let mut stream = Metered::<RecordingRuntime>::to_stream::<CounterPayload>(receiver);
+    let mut context = Context::from_waker(Waker::noop());
+    assert!(matches!(
+        Pin::new(&mut stream).poll_next(&mut context),
+        Poll::Ready(Some(_))
+    ));
+    assert_eq!(depth(), Some(1.0));
+    assert_eq!(
+        metrics.counter(MESSAGES_RECEIVED_TOTAL, &[("channel", "7")]),
+        1
+    );
+
+    handle.inner().fail_sends(Some(SendErrorKind::Full));
+    assert!(handle
+        .try_send(Message::new(1, CounterPayload::Increment(3)))
+        .is_err());
+    handle.inner().fail_sends(Some(SendErrorKind::Closed));
+    assert!(handle
+        .try_send(Message::new(1, CounterPayload::Increment(4)))
+        .is_err());
+    assert_eq!(depth(), Some(1.0));
+    let failures = |kind| metrics.counter(SEND_FAILURES_TOTAL, &[("channel", "7"), ("kind", kind)]);
+    assert_eq!(failures("full"), 1);
+    assert_eq!(failures("closed"), 1);
+
+    set_sink(None);
+}
+
+#[test]
+fn metered_channels_report_to_their_own_sink() {
+    let metrics = Arc::new(InMemoryMetrics::new());
+    type Handle = MeteredHandle<RecordingRuntime, CounterPayload>;
+    let (handle, receiver) = Handle::create_channel_with_sink(8, 8, metrics.clone());
+    let depth = || metrics.gauge(MAILBOX_DEPTH, &[("channel", "8")]);
+
+    handle
+        .try_send(Message::new(1, CounterPayload::Increment(1)))
+        .unwrap();
+    assert_eq!(depth(), Some(1.0));
+
+    let mut stream = Metered::<RecordingRuntime>::to_stream::<CounterPayload>(receiver);
+    let mut context = Context::from_waker(Waker::noop());
+    assert!(matches!(
+        Pin::new(&mut stream).poll_next(&mut context),
+        Poll::Ready(Some(_))
+    ));
+    assert_eq!(depth(), Some(0.0));
+    assert_eq!(
+        metrics.counter(MESSAGES_RECEIVED_TOTAL, &[("channel", "8")]),
+        1
+    );
+
+    handle.inner().fail_sends(Some(SendErrorKind::Full));
+    assert!(handle
+        .try_send(Message::new(1, CounterPayload::Increment(2)))
+        .is_err());
+    assert_eq!(
+        metrics.counter(SEND_FAILURES_TOTAL, &[("channel", "8"), ("kind", "full")]),
+        1
+    );
+}
+
+#[test]
+fn concurrent_senders_leave_the_current_depth() {
+    let metrics = Arc::new(InMemoryMetrics::new());
+    type Handle = MeteredHandle<RecordingRuntime, CounterPayload>;
+    let (handle, _receiver) = Handle::create_channel_with_sink(9, 8, metrics.clone());
+
+    let senders: Vec<_> = (0..4)
+        .map(|_| {
+            let handle = handle.clone();
+            std::thread::spawn(move || {
+                for i in 0..500 {
+                    handle
+                        .try_send(Message::new(1, CounterPayload::Increment(i)))
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for sender in senders {
+        sender.join().unwrap();
+    }
+    assert_eq!(
+        metrics.gauge(MAILBOX_DEPTH, &[("channel", "9")]),
+        Some(2000.0)
+    );
+}
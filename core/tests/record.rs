@@ -0,0 +1,148 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::demo_counter::{components::*, ext_state::*, messaging::*, states::*},
+    messaging::*,
+    observer::Observer,
+    record::*,
+    state_machine::*,
+    testing::*,
+};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+type Harness = StateMachineHarness<CounterComponents<RecordingRuntime>>;
+
+// A writer that can still be read once its recorder has been moved into a state machine
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+type Transitions = Arc<Mutex<Vec<(CounterStateEnum, CounterStateEnum)>>>;
+
+struct TransitionLog(Transitions);
+
+impl Observer<CounterComponents<RecordingRuntime>> for TransitionLog {
+    fn on_transition(&mut self, from: &CounterStateEnum, to: &CounterStateEnum) {
+        self.0.lock().unwrap().push((from.clone(), to.clone()));
+    }
+}
+
+fn counter() -> (Harness, Transitions) {
+    let mut harness = Harness::new(
+        CounterExtendedState::new(CounterInitArgs {
+            supervisor_handle: MockMessageHandle::new(11),
+        }),
+        CounterHandles {
+            standard_handle: MockMessageHandle::new(2),
+            counter_handle: MockMessageHandle::new(3),
+        },
+    );
+    let transitions = Transitions::default();
+    harness
+        .state_machine
+        .add_observer(TransitionLog(transitions.clone()));
+    harness.init(
+        CounterStateEnum::Uninit(Uninit),
+        CounterStateEnum::NotStarted(NotStarted),
+    );
+    (harness, transitions)
+}
+
+fn counter_message(payload: CounterPayload) -> Message<CounterPayload> {
+    Message::new(1, payload)
+}
+
+// Drives a counter through counting to finished, recording what it receives
+fn record_session() -> (Vec<RecordedMessage<CounterRecord>>, Transitions) {
+    let buffer = SharedBuffer::default();
+    let (mut harness, transitions) = counter();
+    harness
+        .state_machine
+        .add_observer(MessageRecorder::new(buffer.clone()));
+    harness
+        .send(counter_message(CounterPayload::SetMax(3)))
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::StartCounting,
+        )))
+        .send(counter_message(CounterPayload::Increment(1)))
+        .send(Message::new(1, StandardPayload::PollState))
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::Reset,
+        )))
+        .send(counter_message(CounterPayload::CountEvent(
+            CountEvent::StartCounting,
+        )))
+        .send(counter_message(CounterPayload::Increment(2)))
+        .expect_state(CounterStateEnum::Finished(Finished));
+
+    let bytes = buffer.0.lock().unwrap().clone();
+    let recording = read_recording(bytes.as_slice()).unwrap();
+    (recording, transitions)
+}
+
+#[test]
+fn replay_reproduces_transitions() {
+    let (recording, recorded_transitions) = record_session();
+    assert_eq!(recording.len(), 7);
+    assert_eq!(recording[0].state, "NotStarted(NotStarted)");
+    assert!(matches!(
+        recording[3].message,
+        CounterRecord::StandardMessage(Message {
+            source_id: 1,
+            payload: StandardRecord::PollState
+        })
+    ));
+
+    let (mut harness, replayed_transitions) = counter();
+    assert_eq!(replay(&mut harness.state_machine, recording).unwrap(), 7);
+    harness.expect_state(CounterStateEnum::Finished(Finished));
+    assert_eq!(
+        *replayed_transitions.lock().unwrap(),
+        *recorded_transitions.lock().unwrap()
+    );
+}
+
+#[test]
+fn replay_stops_where_it_diverges() {
+    let (recording, _) = record_session();
+    let (mut harness, _) = counter();
+    // A counter that is already counting receives the first message in the wrong state
+    harness.send(counter_message(CounterPayload::CountEvent(
+        CountEvent::StartCounting,
+    )));
+    match replay(&mut harness.state_machine, recording) {
+        Err(RecordError::Diverged {
+            index,
+            recorded,
+            actual,
+        }) => {
+            assert_eq!(index, 0);
+            assert_eq!(recorded, "NotStarted(NotStarted)");
+            assert_eq!(actual, "Counting(Counting)");
+        }
+        other => panic!(
+            "Expected divergence, got {:?}",
+            other.map_err(|e| e.to_string())
+        ),
+    }
+}
+
+#[test]
+fn invalid_records_report_their_line() {
+    let input = b"\n{\"timestamp_micros\": 1}\n";
+    match read_recording::<CounterRecord>(&input[..]) {
+        Err(RecordError::Parse { line, .. }) => assert_eq!(line, 2),
+        other => panic!("Expected a parse error, got {:?}", other.map(|r| r.len())),
+    }
+}
@@ -0,0 +1,407 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+// A small blox exercising the state machine itself: internal events posted by states
+// and the order they are handled in, completion events of final states, failing state
+// hooks and the policies for unhandled messages
+
+use bloxide_core::{
+    components::*, messaging::*, observer::*, state_machine::*, testing::*, unhandled::*,
+};
+use std::sync::{Arc, Mutex};
+
+type Harness = StateMachineHarness<TestComponents>;
+
+struct TestComponents;
+
+impl Components for TestComponents {
+    type States = TestState;
+    type MessageSet = TestMessage;
+    type ExtendedState = Log;
+    type Receivers = ();
+    type Handles = ();
+}
+
+#[derive(Default)]
+struct Log {
+    // Values recorded by the states, in the order they were recorded
+    values: Vec<u32>,
+    // Makes Uninit's exit fail
+    fail_uninit_exit: bool,
+}
+
+impl ExtendedState for Log {
+    type InitArgs = ();
+    fn new(_args: Self::InitArgs) -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug)]
+enum TestMessage {
+    /// Records the value
+    Record(u32),
+    /// Records the first value and posts `Record` of the second
+    Post(u32, u32),
+    /// Records the first value, dispatches `Record` of the second from the handler,
+    /// then records the first value + 100
+    Nested(u32, u32),
+    /// Posts `Record` of the value and transitions to `Inner`
+    Enter(u32),
+    /// Transitions from `Inner` to the final state `Done`
+    Finish,
+    /// Transitions to the top-level final state `Stopped`
+    Stop,
+    /// Handled by no state
+    Unknown,
+}
+
+impl MessageSet for TestMessage {
+    fn source_id(&self) -> u16 {
+        1
+    }
+}
+
+// Uninit
+// ├── Idle
+// ├── Composite
+// │   ├── Inner
+// │   └── Done (final)
+// ├── Stopped (final)
+// └── Error
+#[derive(Clone, PartialEq, Debug, Default)]
+enum TestState {
+    #[default]
+    Uninit,
+    Idle,
+    Composite,
+    Inner,
+    Done,
+    Stopped,
+    Error,
+}
+
+impl StateEnum for TestState {
+    fn all_states() -> &'static [Self] {
+        &[
+            TestState::Uninit,
+            TestState::Idle,
+            TestState::Composite,
+            TestState::Inner,
+            TestState::Done,
+            TestState::Stopped,
+            TestState::Error,
+        ]
+    }
+
+    fn error_state() -> Option<Self> {
+        Some(TestState::Error)
+    }
+}
+
+impl State<TestComponents> for TestState {
+    fn parent(&self) -> TestState {
+        match self {
+            TestState::Inner | TestState::Done => TestState::Composite,
+            _ => TestState::Uninit,
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        matches!(self, TestState::Done | TestState::Stopped)
+    }
+
+    // Composite records its completion and goes back to Idle
+    fn on_completion(
+        &self,
+        state_machine: &mut StateMachine<TestComponents>,
+        final_state: &TestState,
+    ) -> Option<TestState> {
+        assert_eq!(*self, TestState::Composite);
+        assert_eq!(*final_state, TestState::Done);
+        state_machine.extended_state.values.push(99);
+        Some(TestState::Idle)
+    }
+
+    fn on_entry(&self, state_machine: &mut StateMachine<TestComponents>) {
+        if *self == TestState::Inner {
+            state_machine.extended_state.values.push(50);
+        }
+    }
+
+    fn try_on_exit(
+        &self,
+        state_machine: &mut StateMachine<TestComponents>,
+    ) -> Result<(), StateError> {
+        if *self == TestState::Uninit && state_machine.extended_state.fail_uninit_exit {
+            return Err(StateError::other("Uninit exit failed"));
+        }
+        Ok(())
+    }
+
+    fn handle_message(
+        &self,
+        state_machine: &mut StateMachine<TestComponents>,
+        message: TestMessage,
+    ) -> Option<Transition<TestState, TestMessage>> {
+        match (self, message) {
+            (_, TestMessage::Record(value)) => state_machine.extended_state.values.push(value),
+            (TestState::Idle, TestMessage::Post(first, second)) => {
+                state_machine.extended_state.values.push(first);
+                state_machine.post(TestMessage::Record(second));
+            }
+            (TestState::Idle, TestMessage::Nested(first, second)) => {
+                state_machine.extended_state.values.push(first);
+                state_machine.dispatch(TestMessage::Record(second));
+                state_machine.extended_state.values.push(first + 100);
+            }
+            (TestState::Idle, TestMessage::Enter(value)) => {
+                state_machine.post(TestMessage::Record(value));
+                return Some(Transition::To(TestState::Inner));
+            }
+            (TestState::Inner, TestMessage::Finish) => {
+                return Some(Transition::To(TestState::Done));
+            }
+            (TestState::Idle, TestMessage::Stop) => {
+                return Some(Transition::To(TestState::Stopped));
+            }
+            (_, message) => return Some(Transition::Parent(message)),
+        }
+        None
+    }
+}
+
+fn idle() -> Harness {
+    let mut harness = Harness::new(Log::default(), ());
+    harness.init(TestState::Uninit, TestState::Idle);
+    harness
+}
+
+fn values(harness: &Harness) -> &[u32] {
+    &harness.state_machine.extended_state.values
+}
+
+#[test]
+fn posted_events_run_before_the_next_message() {
+    let mut harness = idle();
+    harness.send(TestMessage::Post(1, 2));
+    assert_eq!(values(&harness), [1, 2]);
+    harness.send(TestMessage::Record(3));
+    assert_eq!(values(&harness), [1, 2, 3]);
+}
+
+#[test]
+fn posted_events_run_in_order() {
+    let mut harness = idle();
+    harness.state_machine.post(TestMessage::Record(1));
+    harness.state_machine.post(TestMessage::Post(2, 4));
+    harness.state_machine.post(TestMessage::Record(3));
+    harness.send(TestMessage::Record(0));
+    // Events posted while handling a posted event go to the back of the queue
+    assert_eq!(values(&harness), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn dispatch_from_a_handler_is_posted() {
+    let mut harness = idle();
+    harness.send(TestMessage::Nested(1, 2));
+    assert_eq!(values(&harness), [1, 101, 2]);
+}
+
+#[test]
+fn posted_events_run_after_the_transition() {
+    let mut harness = idle();
+    harness
+        .send(TestMessage::Enter(5))
+        .expect_state(TestState::Inner)
+        .expect_events(&[
+            StateEvent::Exited(TestState::Idle),
+            StateEvent::Entered(TestState::Composite),
+            StateEvent::Entered(TestState::Inner),
+        ]);
+    // Handled by the new state, once it has been entered
+    assert_eq!(values(&harness), [50, 5]);
+}
+
+#[test]
+fn nested_final_state_completes_its_composite() {
+    let mut harness = idle();
+    harness
+        .send(TestMessage::Enter(5))
+        .send(TestMessage::Finish);
+    harness.expect_state(TestState::Idle).expect_events(&[
+        StateEvent::Exited(TestState::Inner),
+        StateEvent::Entered(TestState::Done),
+        StateEvent::Exited(TestState::Done),
+        StateEvent::Exited(TestState::Composite),
+        StateEvent::Entered(TestState::Idle),
+    ]);
+    assert_eq!(values(&harness), [50, 5, 99]);
+    // Only a top-level final state finishes the state machine
+    assert!(!harness.state_machine.is_finished());
+    harness.send(TestMessage::Record(1));
+    assert_eq!(values(&harness), [50, 5, 99, 1]);
+}
+
+#[test]
+fn top_level_final_state_finishes() {
+    let mut harness = idle();
+    harness
+        .send(TestMessage::Stop)
+        .expect_state(TestState::Stopped)
+        .expect_finished()
+        // A finished state machine drops messages
+        .send(TestMessage::Record(1));
+    assert!(values(&harness).is_empty());
+}
+
+// Records which observer methods were called
+#[derive(Clone, Default)]
+struct Calls(Arc<Mutex<Vec<&'static str>>>);
+
+impl Observer<TestComponents> for Calls {
+    fn on_receive(&mut self, _state: &TestState, _message: &TestMessage) {
+        self.0.lock().unwrap().push("receive");
+    }
+
+    fn on_message(&mut self, _state: &TestState, _message: &TestMessage) {
+        self.0.lock().unwrap().push("message");
+    }
+
+    fn on_dispatched(&mut self, _state: &TestState) {
+        self.0.lock().unwrap().push("dispatched");
+    }
+
+    fn on_unhandled(&mut self, _state: &TestState, _message: &TestMessage) {
+        self.0.lock().unwrap().push("unhandled");
+    }
+}
+
+#[test]
+fn finished_state_machine_does_not_observe_messages() {
+    let mut harness = idle();
+    let calls = Calls::default();
+    harness.state_machine.add_observer(calls.clone());
+    harness.send(TestMessage::Stop).expect_finished();
+    assert_eq!(
+        *calls.0.lock().unwrap(),
+        ["receive", "message", "dispatched"]
+    );
+
+    calls.0.lock().unwrap().clear();
+    harness.send(TestMessage::Record(1));
+    assert!(calls.0.lock().unwrap().is_empty());
+}
+
+#[test]
+fn failed_uninit_exit_stays_in_the_error_state() {
+    let mut harness = Harness::new(Log::default(), ());
+    harness.state_machine.extended_state.fail_uninit_exit = true;
+    harness
+        .init(TestState::Uninit, TestState::Idle)
+        .expect_state(TestState::Error)
+        // The entry point is never entered
+        .expect_events(&[StateEvent::Entered(TestState::Error)]);
+    assert_eq!(
+        harness.state_machine.last_error(),
+        Some(&StateError::other("Uninit exit failed"))
+    );
+}
+
+// A blox whose states implement neither `handle_message` nor `try_handle_message`
+struct QuietComponents;
+
+impl Components for QuietComponents {
+    type States = QuietState;
+    type MessageSet = TestMessage;
+    type ExtendedState = Log;
+    type Receivers = ();
+    type Handles = ();
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+enum QuietState {
+    #[default]
+    Uninit,
+    Idle,
+}
+
+impl StateEnum for QuietState {
+    fn all_states() -> &'static [Self] {
+        &[QuietState::Uninit, QuietState::Idle]
+    }
+}
+
+impl State<QuietComponents> for QuietState {
+    fn parent(&self) -> QuietState {
+        QuietState::Uninit
+    }
+}
+
+#[test]
+fn states_without_a_handler_forward_to_their_parent() {
+    let mut harness = StateMachineHarness::<QuietComponents>::new(Log::default(), ());
+    harness
+        .state_machine
+        .set_unhandled_policy(UnhandledPolicy::Error);
+    harness
+        .init(QuietState::Uninit, QuietState::Idle)
+        .send(TestMessage::Record(1))
+        .expect_state(QuietState::Idle);
+    assert!(matches!(
+        harness.state_machine.last_error(),
+        Some(StateError::Unhandled(_))
+    ));
+}
+
+// Sends `Unknown` to a blox in `Inner` with the unhandled `policy`
+fn send_unhandled(policy: UnhandledPolicy) -> Harness {
+    let mut harness = idle();
+    harness.state_machine.set_unhandled_policy(policy);
+    harness
+        .send(TestMessage::Enter(5))
+        .send(TestMessage::Unknown);
+    harness
+}
+
+#[test]
+fn unhandled_messages_are_dropped_when_ignored() {
+    send_unhandled(UnhandledPolicy::Ignore)
+        .expect_state(TestState::Inner)
+        .expect_events(&[]);
+}
+
+#[test]
+fn unhandled_messages_are_dropped_when_logged() {
+    let mut harness = send_unhandled(UnhandledPolicy::Log);
+    harness.expect_state(TestState::Inner).expect_events(&[]);
+    assert!(harness.state_machine.last_error().is_none());
+}
+
+#[test]
+fn unhandled_messages_are_sent_as_dead_letters() {
+    let sink = MockMessageHandle::<DeadLetter>::new(9);
+    let mut harness = send_unhandled(UnhandledPolicy::DeadLetter(Box::new(sink.clone())));
+    harness.expect_state(TestState::Inner).expect_events(&[]);
+
+    let sent = sink.take_sent();
+    assert_eq!(sent.len(), 1);
+    let letter = &sent[0].payload;
+    assert_eq!(sent[0].source_id, 1);
+    assert_eq!(letter.source_id, 1);
+    assert_eq!(letter.state_path, ["Uninit", "Composite", "Inner"]);
+    assert_eq!(letter.message, "Unknown");
+}
+
+#[test]
+fn unhandled_messages_are_errors() {
+    let mut harness = send_unhandled(UnhandledPolicy::Error);
+    harness.expect_state(TestState::Error).expect_events(&[
+        StateEvent::Exited(TestState::Inner),
+        StateEvent::Exited(TestState::Composite),
+        StateEvent::Entered(TestState::Error),
+    ]);
+    assert_eq!(
+        harness.state_machine.last_error(),
+        Some(&StateError::Unhandled("Unknown".into()))
+    );
+}
@@ -0,0 +1,157 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::supervisor::{components::*, ext_state::*, messaging::*, states::*},
+    messaging::*,
+    state_machine::*,
+    testing::*,
+};
+
+type Runtime = RecordingRuntime;
+type Harness = StateMachineHarness<SupervisorComponents<Runtime>>;
+
+const ROOT_ID: u16 = 1;
+
+// A running supervisor, returning the root's handle
+fn running_supervisor() -> (Harness, MockMessageHandle<StandardPayload<Runtime>>) {
+    let root = MockMessageHandle::new(ROOT_ID);
+    let mut harness = Harness::new(
+        SupervisorExtendedState::new(SupervisorInitArgs {
+            root_standard_handle: root.clone(),
+            root_future: Box::pin(async {}),
+        }),
+        SupervisorHandles {
+            standard_handle: MockMessageHandle::new(10),
+            supervisor_handle: MockMessageHandle::new(11),
+        },
+    );
+    harness.init(
+        SupervisorStateEnum::Uninit(Uninit),
+        SupervisorStateEnum::Running(Running),
+    );
+    (harness, root)
+}
+
+fn supervisor_message(source_id: u16, payload: SupervisorPayload) -> Message<SupervisorPayload> {
+    Message::new(source_id, payload)
+}
+
+#[test]
+fn init_spawns_the_root() {
+    let (mut harness, root) = running_supervisor();
+    harness
+        .expect_state(SupervisorStateEnum::Running(Running))
+        .expect_nothing_sent_to(&root);
+    assert_eq!(RecordingRuntime::take_spawned().len(), 1);
+}
+
+#[test]
+fn new_handle_is_sent_to_the_requester() {
+    let (mut harness, root) = running_supervisor();
+    harness
+        .send(supervisor_message(
+            ROOT_ID,
+            SupervisorPayload::RequestNewStandardHandle(4),
+        ))
+        .expect_state(SupervisorStateEnum::Running(Running))
+        .expect_sent_to(
+            &root,
+            |p| matches!(p, StandardPayload::StandardChannel(handle, _) if handle.id() == 2),
+        );
+}
+
+#[test]
+fn failed_handle_reply_enters_error_state() {
+    for kind in [SendErrorKind::Full, SendErrorKind::Closed] {
+        let (mut harness, root) = running_supervisor();
+        root.fail_sends(Some(kind));
+        harness
+            .send(supervisor_message(
+                ROOT_ID,
+                SupervisorPayload::RequestNewStandardHandle(4),
+            ))
+            .expect_state(SupervisorStateEnum::Error(Error))
+            .expect_events(&[
+                StateEvent::Exited(SupervisorStateEnum::Running(Running)),
+                StateEvent::Entered(SupervisorStateEnum::Error(Error)),
+            ])
+            .expect_nothing_sent_to(&root);
+        assert!(matches!(
+            harness.state_machine.last_error(),
+            Some(StateError::Send(_))
+        ));
+        // The handle nobody received is forgotten
+        let state = &harness.state_machine.extended_state;
+        assert!(state.parents.is_empty());
+        assert!(!state.blox.contains_key(&2));
+    }
+}
+
+#[test]
+fn handle_request_from_unknown_blox_enters_error_state() {
+    let (mut harness, root) = running_supervisor();
+    harness
+        .send(supervisor_message(
+            7,
+            SupervisorPayload::RequestNewStandardHandle(4),
+        ))
+        .expect_state(SupervisorStateEnum::Error(Error))
+        .expect_nothing_sent_to(&root);
+    assert!(matches!(
+        harness.state_machine.last_error(),
+        Some(StateError::Other(_))
+    ));
+    let state = &harness.state_machine.extended_state;
+    assert!(state.parents.is_empty());
+    assert!(!state.blox.contains_key(&2));
+}
+
+#[test]
+fn child_finished_is_sent_to_the_parent() {
+    let (mut harness, root) = running_supervisor();
+    harness
+        .send(supervisor_message(
+            ROOT_ID,
+            SupervisorPayload::RequestNewStandardHandle(4),
+        ))
+        .expect_sent_to(&root, |p| matches!(p, StandardPayload::StandardChannel(..)))
+        .send(supervisor_message(2, SupervisorPayload::Finished))
+        .expect_sent_to(&root, |p| matches!(p, StandardPayload::ChildFinished(2)))
+        .expect_state(SupervisorStateEnum::Running(Running));
+}
+
+#[test]
+fn failed_child_finished_notice_keeps_running() {
+    let (mut harness, root) = running_supervisor();
+    harness
+        .send(supervisor_message(
+            ROOT_ID,
+            SupervisorPayload::RequestNewStandardHandle(4),
+        ))
+        .expect_sent_to(&root, |p| matches!(p, StandardPayload::StandardChannel(..)));
+    root.fail_sends(Some(SendErrorKind::Full));
+    harness
+        .send(supervisor_message(2, SupervisorPayload::Finished))
+        .expect_state(SupervisorStateEnum::Running(Running))
+        .expect_events(&[]);
+    assert!(harness.state_machine.last_error().is_none());
+
+    // The finished blox was removed either way
+    root.fail_sends(None);
+    harness
+        .send(supervisor_message(2, SupervisorPayload::Finished))
+        .expect_nothing_sent_to(&root);
+}
+
+#[test]
+fn spawn_is_passed_to_the_runtime() {
+    let (mut harness, _root) = running_supervisor();
+    RecordingRuntime::take_spawned();
+    harness
+        .send(supervisor_message(
+            ROOT_ID,
+            SupervisorPayload::Spawn(Box::pin(async {})),
+        ))
+        .expect_state(SupervisorStateEnum::Running(Running));
+    assert_eq!(RecordingRuntime::take_spawned().len(), 1);
+}
@@ -0,0 +1,198 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+use bloxide_core::{
+    blox::demo_counter::{components::*, ext_state::*, messaging::*},
+    components::*,
+    messaging::*,
+    state_machine::*,
+    testing::*,
+};
+use std::fmt::Write;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Debug)]
+struct SpanRecord {
+    name: &'static str,
+    parent: Option<u64>,
+    fields: String,
+}
+
+#[derive(Debug)]
+struct EventRecord {
+    span: Option<u64>,
+    fields: String,
+}
+
+#[derive(Default)]
+struct Captured {
+    // Indexed by span id - 1
+    spans: Vec<SpanRecord>,
+    events: Vec<EventRecord>,
+    entered: Vec<u64>,
+}
+
+// Subscriber keeping every span and event, with the span each was created in
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Captured>>);
+
+// Formats fields as `name=value`, space separated
+struct Fields<'a>(&'a mut String);
+
+impl Visit for Fields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+        let _ = write!(self.0, "{}={:?}", field.name(), value);
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let mut captured = self.0.lock().unwrap();
+        let mut fields = String::new();
+        attributes.record(&mut Fields(&mut fields));
+        let parent = match attributes.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if attributes.is_contextual() => captured.entered.last().copied(),
+            None => None,
+        };
+        captured.spans.push(SpanRecord {
+            name: attributes.metadata().name(),
+            parent,
+            fields,
+        });
+        Id::from_u64(captured.spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut captured = self.0.lock().unwrap();
+        let mut fields = String::new();
+        event.record(&mut Fields(&mut fields));
+        let span = captured.entered.last().copied();
+        captured.events.push(EventRecord { span, fields });
+    }
+
+    fn enter(&self, span: &Id) {
+        self.0.lock().unwrap().entered.push(span.into_u64());
+    }
+
+    fn exit(&self, span: &Id) {
+        let mut captured = self.0.lock().unwrap();
+        let position = captured.entered.iter().rposition(|s| *s == span.into_u64());
+        if let Some(position) = position {
+            captured.entered.remove(position);
+        }
+    }
+}
+
+#[test]
+fn counter_run_loop_is_traced() {
+    let (standard_handle, standard_receiver) =
+        MockMessageHandle::<StandardPayload<RecordingRuntime>>::create_channel_with_size(2, 8);
+    let (counter_handle, counter_receiver) =
+        MockMessageHandle::<CounterPayload>::create_channel_with_size(3, 8);
+    for payload in [
+        CounterPayload::SetMax(1),
+        CounterPayload::CountEvent(CountEvent::StartCounting),
+        CounterPayload::Increment(1),
+    ] {
+        counter_handle.try_send(Message::new(1, payload)).unwrap();
+    }
+    let blox = Blox::<CounterComponents<RecordingRuntime>>::new(
+        CounterReceivers {
+            standard_receiver,
+            counter_receiver,
+        },
+        CounterExtendedState::new(CounterInitArgs {
+            supervisor_handle: MockMessageHandle::new(11),
+        }),
+        CounterHandles {
+            standard_handle,
+            counter_handle,
+        },
+    );
+
+    let capture = Capture::default();
+    tracing::subscriber::with_default(capture.clone(), || {
+        let run = pin!(blox.run_static());
+        let mut context = Context::from_waker(Waker::noop());
+        assert_eq!(run.poll(&mut context), Poll::Ready(()));
+    });
+
+    let captured = capture.0.lock().unwrap();
+    let (blox_span, _) = captured
+        .spans
+        .iter()
+        .enumerate()
+        .find(|(_, s)| s.name == "blox")
+        .expect("no blox span");
+    let blox_span = blox_span as u64 + 1;
+    assert_eq!(
+        captured.spans[blox_span as usize - 1].fields,
+        "blox.name=\"counter\" blox.id=2"
+    );
+
+    let dispatches: Vec<u64> = (1..=captured.spans.len() as u64)
+        .filter(|id| captured.spans[*id as usize - 1].name == "dispatch")
+        .collect();
+    let span = |id: u64| &captured.spans[id as usize - 1];
+    assert_eq!(dispatches.len(), 3);
+    assert!(dispatches
+        .iter()
+        .all(|d| span(*d).parent == Some(blox_span)));
+    // Only the variant name, the whole message is in a trace event inside the span
+    assert_eq!(
+        span(dispatches[0]).fields,
+        "state=NotStarted(NotStarted) message=CounterMessage"
+    );
+    assert_eq!(
+        span(dispatches[2]).fields,
+        "state=Counting(Counting) message=CounterMessage"
+    );
+    let message_in = |id: u64| {
+        let event = captured
+            .events
+            .iter()
+            .find(|e| e.span == Some(id) && !e.fields.starts_with("message=transition"))
+            .expect("no message event");
+        event.fields.clone()
+    };
+    assert!(message_in(dispatches[0]).contains("SetMax(1)"));
+    assert!(message_in(dispatches[1]).contains("StartCounting"));
+
+    // Init transitions in the blox span, the others in the dispatch that caused them
+    let transitions: Vec<&EventRecord> = captured
+        .events
+        .iter()
+        .filter(|e| e.fields.starts_with("message=transition"))
+        .collect();
+    assert_eq!(transitions.len(), 3);
+    assert_eq!(transitions[0].span, Some(blox_span));
+    assert!(transitions[0]
+        .fields
+        .ends_with("from=Uninit(Uninit) to=NotStarted(NotStarted)"));
+    assert_eq!(transitions[1].span, Some(dispatches[1]));
+    assert!(transitions[1]
+        .fields
+        .ends_with("from=NotStarted(NotStarted) to=Counting(Counting)"));
+    assert_eq!(transitions[2].span, Some(dispatches[2]));
+    assert!(message_in(dispatches[2]).contains("Increment(1)"));
+    assert!(transitions[2]
+        .fields
+        .ends_with("from=Counting(Counting) to=Finished(Finished)"));
+}
@@ -0,0 +1,19 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! Instantiates the demo bloxes on `no_std` runtimes so the whole generic stack is
+//! type checked and monomorphized without std.  Build it on its own, once with alloc
+//! and once without:
+//!
+//! ```text
+//! cargo build -p bloxide-no-std-check
+//! cargo build -p bloxide-no-std-check --no-default-features
+//! ```
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+pub mod with_alloc;
+pub mod without_alloc;
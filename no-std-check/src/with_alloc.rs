@@ -0,0 +1,154 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! The demo bloxes on a `no_std + alloc` runtime
+
+use bloxide_core::{
+    blox::{
+        demo_counter::{components::*, ext_state::*},
+        demo_root::{components::*, ext_state::*},
+        supervisor::{components::*, ext_state::*, messaging::*},
+    },
+    components::*,
+    messaging::*,
+    state_machine::*,
+    std_exports::*,
+};
+use core::task::{Context, Poll};
+use futures_core::Stream;
+
+/// Runtime with no executor, spawned futures are dropped
+#[derive(Clone)]
+pub struct NoStdRuntime;
+
+impl Runtime for NoStdRuntime {
+    type MessageHandle<P: Send + 'static> = NullHandle<P>;
+
+    fn spawn<F>(f: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        drop(f);
+    }
+
+    type ReceiverStream<P: Send + 'static> = NullReceiver<P>;
+
+    fn to_stream<P: Send + 'static>(
+        receiver: <Self::MessageHandle<P> as MessageSender>::ReceiverType,
+    ) -> Self::ReceiverStream<P> {
+        receiver
+    }
+}
+
+impl DynamicRuntime for NoStdRuntime {}
+
+/// Handle that accepts and drops every message
+pub struct NullHandle<P> {
+    id: u16,
+    phantom: PhantomData<fn() -> P>,
+}
+
+impl<P> Clone for NullHandle<P> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<P: Send + 'static> MessageSender for NullHandle<P> {
+    type PayloadType = P;
+    type SenderType = NullHandle<P>;
+    type ReceiverType = NullReceiver<P>;
+    type ErrorType = ();
+
+    fn try_send(&self, _msg: Message<P>) -> Result<(), Self::ErrorType> {
+        Ok(())
+    }
+
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn create_channel_with_size(id: u16, _size: usize) -> (Self, Self::ReceiverType) {
+        (
+            Self {
+                id,
+                phantom: PhantomData,
+            },
+            NullReceiver {
+                phantom: PhantomData,
+            },
+        )
+    }
+}
+
+/// Receiver that never yields a message
+pub struct NullReceiver<P> {
+    phantom: PhantomData<fn() -> P>,
+}
+
+impl<P> Stream for NullReceiver<P> {
+    type Item = Message<P>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(None)
+    }
+}
+
+type BloxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+pub fn counter_blox(supervisor_handle: NullHandle<SupervisorPayload>) -> BloxFuture {
+    let (standard_handle, standard_receiver) = NullHandle::create_channel_with_size(3, 1);
+    let (counter_handle, counter_receiver) = NullHandle::create_channel_with_size(4, 1);
+    let blox = Blox::<CounterComponents<NoStdRuntime>>::new(
+        CounterReceivers {
+            standard_receiver,
+            counter_receiver,
+        },
+        CounterExtendedState::new(CounterInitArgs { supervisor_handle }),
+        CounterHandles {
+            standard_handle,
+            counter_handle,
+        },
+    );
+    Box::new(blox).run()
+}
+
+pub fn root_blox(supervisor_handle: NullHandle<SupervisorPayload>) -> BloxFuture {
+    let (standard_handle, std_rx) = NullHandle::create_channel_with_size(1, 1);
+    let (counter_handle, counter_rx) = NullHandle::create_channel_with_size(2, 1);
+    let blox = Blox::<RootComponents<NoStdRuntime>>::new(
+        RootReceivers { std_rx, counter_rx },
+        RootExtState::new(RootInitArgs {
+            supervisor_handle,
+            counter_handle: None,
+        }),
+        RootHandles {
+            standard_handle,
+            counter_handle,
+        },
+    );
+    Box::new(blox).run()
+}
+
+pub fn supervisor_blox() -> BloxFuture {
+    let (standard_handle, standard_receiver) = NullHandle::create_channel_with_size(10, 1);
+    let (supervisor_handle, supervisor_receiver) = NullHandle::create_channel_with_size(11, 1);
+    let (root_standard_handle, _) = NullHandle::create_channel_with_size(1, 1);
+    let blox = Blox::<SupervisorComponents<NoStdRuntime>>::new(
+        SupervisorReceivers {
+            standard_receiver,
+            supervisor_receiver,
+        },
+        SupervisorExtendedState::new(SupervisorInitArgs {
+            root_standard_handle,
+            root_future: root_blox(supervisor_handle.clone()),
+        }),
+        SupervisorHandles {
+            standard_handle,
+            supervisor_handle,
+        },
+    );
+    Box::new(blox).run()
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 Bloxide, all rights reserved
+
+//! The counter blox on `EmbassyStaticRuntime`, with static channels and a static task
+//! pool, so it runs without a global allocator, and its messages encoded with postcard
+
+use bloxide_core::{
+    blox::{
+        demo_counter::{components::*, ext_state::*, messaging::*},
+        supervisor::messaging::*,
+    },
+    codec::*,
+    components::*,
+    messaging::*,
+    state_machine::*,
+};
+use bloxide_embassy::{blox_task, static_channel, EmbassyStaticRuntime, StaticMessageHandle};
+use embassy_executor::{SpawnError, Spawner};
+
+pub type StaticCounterBlox = Blox<CounterComponents<EmbassyStaticRuntime>>;
+
+blox_task!(counter_task, StaticCounterBlox);
+
+/// Creates the counter blox and spawns it, returning its counter handle
+pub fn spawn_counter(
+    spawner: Spawner,
+    supervisor_handle: StaticMessageHandle<SupervisorPayload>,
+) -> Result<StaticMessageHandle<CounterPayload>, SpawnError> {
+    let (standard_handle, standard_receiver) =
+        static_channel!(3, StandardPayload<EmbassyStaticRuntime>);
+    let (counter_handle, counter_receiver) = static_channel!(4, CounterPayload);
+    let blox = StaticCounterBlox::new(
+        CounterReceivers {
+            standard_receiver,
+            counter_receiver,
+        },
+        CounterExtendedState::new(CounterInitArgs { supervisor_handle }),
+        CounterHandles {
+            standard_handle,
+            counter_handle,
+        },
+    );
+    spawner.spawn(counter_task(blox))?;
+    Ok(counter_handle)
+}
+
+/// Encodes a counter message for the blox `to` with postcard, into an inline buffer
+pub fn encode_counter_message(
+    message: &Message<CounterPayload>,
+    to: u16,
+) -> Result<RawPayload, CodecError> {
+    message.to_raw::<Postcard>(to)
+}